@@ -1,14 +1,21 @@
 use std::{
 	env::consts,
-	io::{self, stdout, Error},
+	fs::OpenOptions,
+	io::{self, stdout, Error, IsTerminal, Write},
 	rc::Rc,
 };
 
 use crossterm::{
 	cursor::EnableBlinking,
-	event::{KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
+	event::{
+		DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+		KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+	},
 	execute,
-	terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+	terminal::{
+		disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
+		LeaveAlternateScreen,
+	},
 };
 use ratatui::{
 	layout::{Constraint, Direction, Layout, Rect},
@@ -22,12 +29,19 @@ use ratatui::{
 use config::config::Config;
 use editor::editor::EditorSpace;
 
+// The terminal that the ui is drawn to (stdout or the controlling terminal)
+type AppTerminal = Terminal<CrosstermBackend<Box<dyn Write>>>;
+
 // Main driver function
 pub fn run(filename: String) -> io::Result<()> {
 	// Initialize the config and terminal
 	let (config, mut terminal) = init()?;
 	// Struct to track the entire editing space
 	let mut editor_space = EditorSpace::new(filename, config);
+	// Without keyboard enhancements, the terminal sends some control keys as others
+	editor_space.set_legacy_keys(
+		consts::OS != "windows" && !supports_keyboard_enhancement().unwrap_or(false),
+	);
 
 	// Flag to break the below loop (ending app execution)
 	let mut break_loop = false;
@@ -41,6 +55,11 @@ pub fn run(filename: String) -> io::Result<()> {
 		})?;
 		// Get input within the editor space
 		editor_space.handle_input(&mut break_loop);
+		// Copy text to the terminal's clipboard if the system clipboard isn't used
+		if let Some(sequence) = editor_space.take_clipboard_sequence() {
+			write!(terminal.backend_mut(), "{}", sequence)?;
+			terminal.backend_mut().flush()?;
+		}
 		// Check if user wants to quit the app
 		if break_loop {
 			break;
@@ -48,75 +67,112 @@ pub fn run(filename: String) -> io::Result<()> {
 	}
 
 	// Reset variables when leaving the app
-	end()?;
+	end(&mut terminal)?;
+
+	// If used as a filter (with stdout redirected), write the final buffer to stdout
+	if editor_space.is_piped() && !stdout().is_terminal() {
+		editor_space.write_buffer(&mut stdout())?;
+	}
 
 	Ok(())
 }
 
 // Initialize the terminal and the config
-fn init() -> Result<(Config, Terminal<CrosstermBackend<io::Stdout>>), Error> {
+fn init() -> Result<(Config, AppTerminal), Error> {
 	// Create a default config
 	let config = Config::default();
+	// Get the output that the ui is drawn to
+	let mut output = open_output()?;
 
 	// Put stdout into raw mode (turn off canonical mode)
 	enable_raw_mode()?;
-	// Switches the terminal to an alternate screen and changes the cursor
+	/* Switches the terminal to an alternate screen, changes the cursor, captures the mouse,
+	and receives pasted text all at once (bracketed paste) */
 	execute!(
-		stdout(),
+		output,
 		EnterAlternateScreen,
 		EnableBlinking,
 		config.cursor_style,
+		EnableMouseCapture,
+		EnableBracketedPaste,
 	)?;
 	// Only enable keyboard enhancments if not on windows
 	if consts::OS != "windows" {
 		execute!(
-			stdout(),
+			output,
 			PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
 		)?;
 	}
 
 	// Create a new terminal
-	let terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+	let terminal = Terminal::new(CrosstermBackend::new(output))?;
 
 	// Return the config and terminal
 	Ok((config, terminal))
 }
 
+/* Get the output that the ui is drawn to. This is stdout unless stdout
+is redirected (when used as a filter), in which case the ui is drawn
+directly to the controlling terminal. */
+fn open_output() -> Result<Box<dyn Write>, Error> {
+	// If stdout is a terminal, draw to it
+	if stdout().is_terminal() {
+		return Ok(Box::new(stdout()));
+	}
+	// The controlling terminal device
+	let tty = match consts::OS {
+		"windows" => "CONOUT$",
+		_ => "/dev/tty",
+	};
+	// Open the controlling terminal for writing
+	Ok(Box::new(OpenOptions::new().write(true).open(tty)?))
+}
+
 // Define the frame ui
 fn ui(frame: &mut Frame, editor: &mut EditorSpace) {
 	// Create the layout for the line numbers and editor widgets
-	let (editor_layout, keybinds_layout) = build_layout(frame);
+	let (editor_layout, keybinds_layout) = build_layout(frame, editor);
 	// Render the editor and line numbers ui
 	editor.render_ui(frame, editor_layout);
+	// Render the status bar
+	editor.render_status_bar(frame, keybinds_layout[1]);
 	// Render the keybinds
 	render_keybinds(frame, keybinds_layout, editor);
 }
 
 // Build the layout for displaying the widgets
-fn build_layout(frame: &mut Frame) -> (Rc<[Rect]>, Rc<[Rect]>) {
+fn build_layout(frame: &mut Frame, editor: &EditorSpace) -> (Rc<[Rect]>, Rc<[Rect]>) {
 	/* The height of the widget displaying all keybindings.
 	3 because 1 line of text and 1 line for the bottom border */
 	let keybinds_height = 3;
+	// The height of the status bar
+	let status_height = 1;
+	// The height of the editor (the terminal may be shorter than the other widgets)
+	let editor_height = frame
+		.size()
+		.height
+		.saturating_sub(keybinds_height + status_height);
 	// The vertical split of the frame
 	let outer_layout = Layout::new(
 		Direction::Vertical,
 		[
-			Constraint::Length(frame.size().height - keybinds_height),
+			Constraint::Length(editor_height),
+			Constraint::Length(status_height),
 			Constraint::Length(keybinds_height),
 		],
 	)
 	// Split over the entire frame
 	.split(frame.size());
 	/* The width of the widget that displays the line numbers.
-	This should be 2 greater than the number of digits
-	to display (9 = 7 digits). */
-	let line_nums_width = 9;
+	This is 2 greater than the number of digits to display
+	(and 0 if line numbers are hidden). */
+	let line_nums_width = editor.gutter_width();
 	// Create the layout for the EditorSpace and the line numbers
 	let editor_layout = Layout::new(
 		Direction::Horizontal,
 		[
 			Constraint::Length(line_nums_width),
-			Constraint::Length(frame.size().width - line_nums_width),
+			Constraint::Length(frame.size().width.saturating_sub(line_nums_width)),
 		],
 	)
 	// Split it over the top widget from the outer_layout
@@ -129,8 +185,8 @@ fn build_layout(frame: &mut Frame) -> (Rc<[Rect]>, Rc<[Rect]>) {
 fn render_keybinds(frame: &mut Frame, layout: Rc<[Rect]>, editor: &mut EditorSpace) {
 	// The keybinds that are displayed
 	let keybinds = format!(
-		"\'<^s> Save\' \t \'<^q> Quit\' \t \'<^c> Copy\' \t \'<^x> Cut\'\n\
-         \'<^p> Paste\' {} \'<^z> Undo\' \t \'<^r> Redo\' \t \'<^Arrows> Jump\'",
+		"\'<^s> Save\' \t \'<^q> Quit\' \t \'<^c> Copy\' \t \'<^x> Cut\' \t \'<^y>/<^p> Registers\'\n\
+         \'<^v> Paste\' {} \'<^z> Undo\' \t \'<^r> Redo\' \t \'<^Arrows> Jump\'",
 		&" ".repeat(editor.config.tab_width - 1)
 	)
 	.replace('\t', &" ".repeat(editor.config.tab_width));
@@ -142,20 +198,25 @@ fn render_keybinds(frame: &mut Frame, layout: Rc<[Rect]>, editor: &mut EditorSpa
 				.borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
 				.border_type(BorderType::Thick),
 		),
-		layout[1],
+		layout[2],
 	);
 }
 
 // Reset the terminal before exiting the app
-fn end() -> io::Result<()> {
+fn end(terminal: &mut AppTerminal) -> io::Result<()> {
 	// Turn off raw mode for stdout (enable canonical mode)
 	disable_raw_mode()?;
-	// Exit the alternate screen
-	execute!(stdout(), LeaveAlternateScreen)?;
+	// Exit the alternate screen (and stop capturing the mouse and pasted text)
+	execute!(
+		terminal.backend_mut(),
+		DisableBracketedPaste,
+		DisableMouseCapture,
+		LeaveAlternateScreen
+	)?;
 
 	// Keyboard enhancements were only enabled on non-windows platforms
 	if consts::OS != "windows" {
-		execute!(stdout(), PopKeyboardEnhancementFlags)?;
+		execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
 	}
 
 	Ok(())
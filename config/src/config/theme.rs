@@ -13,10 +13,52 @@ pub struct Theme {
 	pub line_highlight_bg_color: Color,
 	// Color of the highlighted selection of text
 	pub selection_highlight: Color,
+	// Background color of the bracket at the cursor and its matching bracket
+	pub bracket_match_bg: Color,
 	// Selected tab's foreground (text) color
 	pub tab_fg: Color,
 	// Selected tab's backgound (highlight) color
 	pub tab_bg: Color,
+	// Status bar's foreground (text) color
+	pub status_bar_fg: Color,
+	// Status bar's background color
+	pub status_bar_bg: Color,
+	// Color of the transient messages in the status bar
+	pub status_message_fg: Color,
+	// Color of visible whitespace markers
+	pub whitespace_fg: Color,
+	// Background color of trailing whitespace (when whitespace is visible)
+	pub trailing_whitespace_bg: Color,
+	// Color of the indentation guides
+	pub indent_guide_fg: Color,
+	// Background color of the rulers
+	pub ruler_bg: Color,
+	// Color of text past the line length limit
+	pub long_line_fg: Color,
+	// Syntax highlighting color of keywords
+	pub syntax_keyword: Color,
+	// Syntax highlighting color of type names
+	pub syntax_type: Color,
+	// Syntax highlighting color of function names
+	pub syntax_function: Color,
+	// Syntax highlighting color of attributes, macros, decorators, and variables
+	pub syntax_attribute: Color,
+	// Syntax highlighting color of string literals
+	pub syntax_string: Color,
+	// Syntax highlighting color of number literals
+	pub syntax_number: Color,
+	// Syntax highlighting color of built in constants (e.g. true, false, None)
+	pub syntax_constant: Color,
+	// Syntax highlighting color of comments
+	pub syntax_comment: Color,
+	// Syntax highlighting color of keys (e.g. in TOML and JSON)
+	pub syntax_key: Color,
+	// Syntax highlighting color of headings (e.g. in Markdown)
+	pub syntax_heading: Color,
+	// Syntax highlighting color of emphasized text (e.g. in Markdown)
+	pub syntax_emphasis: Color,
+	// Syntax highlighting color of links (e.g. in Markdown)
+	pub syntax_link: Color,
 }
 
 impl Theme {
@@ -28,8 +70,29 @@ impl Theme {
 			line_highlight_fg_color: Color::White,
 			line_highlight_bg_color: Color::DarkGray,
 			selection_highlight: Color::Rgb(80, 100, 150),
+			bracket_match_bg: Color::Rgb(90, 90, 110),
 			tab_fg: Color::White,
 			tab_bg: Color::Blue,
+			status_bar_fg: Color::White,
+			status_bar_bg: Color::Rgb(40, 40, 60),
+			status_message_fg: Color::LightYellow,
+			whitespace_fg: Color::DarkGray,
+			trailing_whitespace_bg: Color::Rgb(90, 30, 30),
+			indent_guide_fg: Color::DarkGray,
+			ruler_bg: Color::Rgb(45, 45, 45),
+			long_line_fg: Color::LightRed,
+			syntax_keyword: Color::LightMagenta,
+			syntax_type: Color::LightYellow,
+			syntax_function: Color::LightBlue,
+			syntax_attribute: Color::Cyan,
+			syntax_string: Color::LightGreen,
+			syntax_number: Color::LightRed,
+			syntax_constant: Color::LightRed,
+			syntax_comment: Color::Gray,
+			syntax_key: Color::LightCyan,
+			syntax_heading: Color::LightBlue,
+			syntax_emphasis: Color::Yellow,
+			syntax_link: Color::Cyan,
 		}
 	}
 
@@ -41,8 +104,29 @@ impl Theme {
 			line_highlight_fg_color: Color::Black,
 			line_highlight_bg_color: Color::Gray,
 			selection_highlight: Color::LightBlue,
+			bracket_match_bg: Color::Rgb(200, 200, 160),
 			tab_fg: Color::Black,
 			tab_bg: Color::LightBlue,
+			status_bar_fg: Color::Black,
+			status_bar_bg: Color::Rgb(210, 210, 225),
+			status_message_fg: Color::Rgb(150, 100, 0),
+			whitespace_fg: Color::Gray,
+			trailing_whitespace_bg: Color::Rgb(240, 190, 190),
+			indent_guide_fg: Color::Gray,
+			ruler_bg: Color::Rgb(230, 230, 230),
+			long_line_fg: Color::Red,
+			syntax_keyword: Color::Magenta,
+			syntax_type: Color::Rgb(150, 100, 0),
+			syntax_function: Color::Blue,
+			syntax_attribute: Color::Cyan,
+			syntax_string: Color::Green,
+			syntax_number: Color::Red,
+			syntax_constant: Color::Red,
+			syntax_comment: Color::DarkGray,
+			syntax_key: Color::Blue,
+			syntax_heading: Color::Blue,
+			syntax_emphasis: Color::Rgb(150, 100, 0),
+			syntax_link: Color::Cyan,
 		}
 	}
 }
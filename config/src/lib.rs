@@ -1,10 +1,10 @@
 pub mod config {
-	use std::default::Default;
+	use std::{collections::HashMap, default::Default};
 
 	use crossterm::cursor::SetCursorStyle;
 
 	// Contains color settings
-	mod theme;
+	pub mod theme;
 
 	// Contains user configuration for the app
 	#[derive(Clone)]
@@ -13,8 +13,79 @@ pub mod config {
 		pub cursor_style: SetCursorStyle,
 		// The number of spaces used to represent a tab character
 		pub tab_width: usize,
+		// Flag to indent with `tab_width` spaces instead of tab characters
+		pub soft_tabs: bool,
+		// Flag to detect the indentation (tabs or spaces, and its width) of a file when it's opened
+		pub detect_indent: bool,
 		// The color theme of the editor
 		pub theme: theme::Theme,
+		// Flag to wrap long lines across multiple rows of the editor
+		pub soft_wrap: bool,
+		// How line numbers are shown next to the editor
+		pub line_numbers: LineNumbers,
+		// Which whitespace is shown with visible markers
+		pub whitespace: Whitespace,
+		// Flag to draw vertical lines at each level of indentation
+		pub indent_guides: bool,
+		// Columns to draw vertical rulers at (e.g. 100 draws a ruler right after the 100th column)
+		pub rulers: Vec<usize>,
+		// Text past this many columns of a line is colored
+		pub line_limit: Option<usize>,
+		// Which clipboard text is copied to (and pasted from)
+		pub clipboard: ClipboardPreference,
+		// How new lines are indented
+		pub auto_indent: AutoIndent,
+		// The auto indent of specific languages by name (e.g. "Python"), overriding `auto_indent`
+		pub language_indent: HashMap<String, AutoIndent>,
+		// Flag to insert the closing bracket (or quote) after typing an opening one
+		pub auto_pair: bool,
+	}
+
+	// The ways new lines can be indented
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum AutoIndent {
+		// New lines start at the beginning of the line
+		Off,
+		// New lines keep the indentation of the previous line
+		Keep,
+		/* New lines keep the indentation of the previous line, indent further after an opening
+		bracket (or a colon in Python), and closing brackets de-indent */
+		Smart,
+	}
+
+	// The clipboards that the editor can use
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum ClipboardPreference {
+		// The system clipboard (falling back to the terminal's clipboard if it isn't available)
+		System,
+		// The terminal's clipboard (using OSC 52 escape sequences, e.g. over SSH)
+		Osc52,
+		// A clipboard that only exists within the editor
+		Internal,
+	}
+
+	// The ways line numbers can be shown next to the editor
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum LineNumbers {
+		// The line number of every line
+		Absolute,
+		// The distance of every line from the cursor's line
+		Relative,
+		// The line number of the cursor's line and the distance of every other line from it
+		Hybrid,
+		// Don't show line numbers
+		Hidden,
+	}
+
+	// The ways whitespace can be shown in the editor
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum Whitespace {
+		// Whitespace is blank
+		Hidden,
+		// Only trailing whitespace (and special spaces) are marked
+		Trailing,
+		// All whitespace is marked (dots for spaces and arrows for tabs)
+		All,
 	}
 
 	impl Default for Config {
@@ -25,8 +96,29 @@ pub mod config {
 				cursor_style: SetCursorStyle::DefaultUserShape,
 				// Set the number of spaces for a tab to 4
 				tab_width: 4,
+				// Indent with tabs unless the file is indented with spaces
+				soft_tabs: false,
+				detect_indent: true,
 				// Set the theme as a default dark theme based on the terminal theme
 				theme: theme::Theme::dark_terminal(),
+				// Long lines aren't wrapped by default
+				soft_wrap: false,
+				// Show the line number of every line
+				line_numbers: LineNumbers::Absolute,
+				// Whitespace is blank by default
+				whitespace: Whitespace::Hidden,
+				// Draw indentation guides
+				indent_guides: true,
+				// No rulers or line length limit by default
+				rulers: Vec::new(),
+				line_limit: None,
+				// Use the system clipboard if it's available
+				clipboard: ClipboardPreference::System,
+				// Smart indentation for every language
+				auto_indent: AutoIndent::Smart,
+				language_indent: HashMap::new(),
+				// Close brackets and quotes automatically
+				auto_pair: true,
 			}
 		}
 	}
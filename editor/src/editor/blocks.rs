@@ -1,6 +1,9 @@
-use super::EditorSpace;
+use super::{
+	syntax::{LineHighlight, SyntaxState},
+	EditorSpace,
+};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::io::Error;
+use std::{collections::HashMap, io::Error};
 use unicode_segmentation::UnicodeSegmentation;
 
 mod text_block;
@@ -23,6 +26,8 @@ pub struct Blocks {
 	pub starting_line_num: usize,
 	// The ID number of the last block
 	pub tail_block: usize,
+	// The syntax state at the start of each block that's been highlighted (by block number)
+	pub start_states: HashMap<usize, SyntaxState>,
 }
 
 impl Blocks {
@@ -31,7 +36,7 @@ impl Blocks {
 		// Ensure that the metadata of the file is up to date
 		editor.file.sync_all()?;
 		// Get the number of bytes in the file
-		let size = editor.file.metadata()?.len() as usize;
+		let size = editor.file.size()? as usize;
 		// Find the max number of blocks for this file
 		let mut max_blocks = size.div_ceil(text_block::BLOCK_SIZE as usize);
 		// Can't have max of 0 blocks
@@ -49,6 +54,7 @@ impl Blocks {
 			// Calculate the line number of the first line
 			starting_line_num: TextBlock::calc_line_num(editor, block_num, max_blocks)?,
 			tail_block: block_num,
+			start_states: HashMap::new(),
 		};
 		// The current location in the block
 		let location = blocks.get_location(line_num)?;
@@ -160,6 +166,8 @@ impl Blocks {
 		let [block_num, line_num] = self.curr_position;
 		// Insert the character into the correct block on the correct line
 		self.blocks_list[block_num].content[line_num].insert(text_position, character);
+		// The line needs to be highlighted again
+		self.blocks_list[block_num].highlights[line_num] = None;
 
 		// Set this block as modified
 		self.blocks_list[block_num].is_modified = true;
@@ -181,6 +189,11 @@ impl Blocks {
 			.insert(line_num + 1, String::from(after_cursor));
 		// Remove the rest of the old row after the enter
 		self.blocks_list[block_num].content[line_num].truncate(text_position);
+		// Both rows need to be highlighted
+		self.blocks_list[block_num].highlights[line_num] = None;
+		self.blocks_list[block_num]
+			.highlights
+			.insert(line_num + 1, None);
 
 		// Set this block as modified
 		self.blocks_list[block_num].is_modified = true;
@@ -211,6 +224,8 @@ impl Blocks {
 		line_str.extend(line.iter().copied());
 		// Set the line in the block to this new line
 		self.blocks_list[block_num].content[line_num] = line_str;
+		// The line needs to be highlighted again
+		self.blocks_list[block_num].highlights[line_num] = None;
 
 		// Set this block as modified
 		self.blocks_list[block_num].is_modified = true;
@@ -227,6 +242,8 @@ impl Blocks {
 		// Reduce the length of this block
 		self.blocks_list[block_num].len -= 1;
 
+		// Remove the highlighting of the line
+		self.blocks_list[block_num].highlights.remove(line_num);
 		// Remove (and return) the below line
 		Ok(self.blocks_list[block_num].content.remove(line_num))
 	}
@@ -243,6 +260,8 @@ impl Blocks {
 		let [block_num, line_num] = self.curr_position;
 		// Append the rest of the below line to the current line (where the cursor is moving to)
 		self.blocks_list[block_num].content[line_num].push_str(after_cursor);
+		// The line needs to be highlighted again
+		self.blocks_list[block_num].highlights[line_num] = None;
 
 		// Set the current block as modified
 		self.blocks_list[block_num].is_modified = true;
@@ -292,6 +311,10 @@ impl Blocks {
 		let [block_num, line_num] = self.curr_position;
 		// Update the line
 		self.blocks_list[block_num].content[line_num] = text;
+		// The line needs to be highlighted again
+		self.blocks_list[block_num].highlights[line_num] = None;
+		// Set this block as modified
+		self.blocks_list[block_num].is_modified = true;
 	}
 
 	// Update the given line (slower than current line)
@@ -300,15 +323,19 @@ impl Blocks {
 		let (block_num, line_num) = self.get_location(line_num)?;
 		// Update the line
 		self.blocks_list[block_num].content[line_num] = text;
+		// The line needs to be highlighted again
+		self.blocks_list[block_num].highlights[line_num] = None;
+		// Set this block as modified
+		self.blocks_list[block_num].is_modified = true;
 
 		Ok(())
 	}
 
 	// Check that the Blocks is valid for the current widget
 	pub fn check_blocks(&mut self, editor: &mut EditorSpace) {
-		/* If the Blocks is too short, but there is more text to be shown,
-		add a new TextBlock to the tail. */
-		if self.len() < editor.height + editor.scroll_offset
+		/* While the Blocks is too short, but there is more text to be shown,
+		add a new TextBlock to the tail (e.g. after the widget grows taller). */
+		while self.len() < editor.height + editor.scroll_offset
 			&& editor.file_length > editor.height
 			&& self.tail_block < self.max_blocks - 1
 		{
@@ -317,6 +344,72 @@ impl Blocks {
 		}
 	}
 
+	/* Highlight any lines in the loaded TextBlocks that were edited since they were
+	last highlighted (see TextBlock::update_highlights). */
+	pub fn update_highlights(
+		&mut self,
+		highlighter: impl Fn(usize, &str, SyntaxState) -> LineHighlight,
+	) {
+		// The state at the end of the previous TextBlock (starting with the state before the head)
+		let mut state = self.head_state();
+		// The line number of the first line of the current TextBlock
+		let mut line_num = self.starting_line_num;
+		for block in self.blocks_list.iter_mut() {
+			// Remember the state for when the block is the head block
+			self.start_states.insert(block.block_num, state);
+			state = block.update_highlights(line_num, state, &highlighter);
+			line_num += block.len;
+		}
+	}
+
+	/* Find the syntax state at the start of the head block if it isn't known (e.g. it was
+	loaded before the blocks above it were highlighted) by highlighting the unloaded blocks
+	from the nearest block before it with a known state. */
+	pub fn update_head_state(
+		&mut self,
+		editor: &mut EditorSpace,
+		highlighter: impl Fn(usize, &str, SyntaxState) -> LineHighlight,
+	) -> Result<(), Error> {
+		if self.head_block == 0 || self.start_states.contains_key(&self.head_block) {
+			return Ok(());
+		}
+
+		// The nearest block with a known state (the first block starts in the default state)
+		let mut block_num = (1..self.head_block)
+			.rev()
+			.find(|num| self.start_states.contains_key(num))
+			.unwrap_or(0);
+		let mut state = self
+			.start_states
+			.get(&block_num)
+			.copied()
+			.unwrap_or_default();
+		let mut line_num = TextBlock::calc_line_num(editor, block_num, self.max_blocks)?;
+		// Highlight each block up to the head block
+		while block_num < self.head_block {
+			let mut block = TextBlock::new(editor, block_num, self.max_blocks)?;
+			state = block.update_highlights(line_num, state, &highlighter);
+			line_num += block.len;
+			block_num += 1;
+			self.start_states.insert(block_num, state);
+		}
+		Ok(())
+	}
+
+	// Clear the cached highlighting of the given range of line numbers (so they're highlighted again)
+	#[cfg(feature = "tree-sitter")]
+	pub fn clear_highlights(&mut self, lines: std::ops::Range<usize>) {
+		let mut line_num = self.starting_line_num;
+		for block in self.blocks_list.iter_mut() {
+			for highlight in block.highlights.iter_mut() {
+				if lines.contains(&line_num) {
+					*highlight = None;
+				}
+				line_num += 1;
+			}
+		}
+	}
+
 	// Load in all TextBlocks of a file into one Blocks
 	pub fn load_all_blocks(&mut self, editor: &mut EditorSpace) {
 		// The block number of the head and tail blocks respectively
@@ -361,7 +454,7 @@ impl Blocks {
 	}
 
 	// Return a tuple containing (block number, line number) for accessing the block content
-	fn get_location(&self, line_num: usize) -> Result<(usize, usize), Error> {
+	pub fn get_location(&self, line_num: usize) -> Result<(usize, usize), Error> {
 		// Track the total lines over the blocks
 		let mut lines = self.starting_line_num;
 		// The starting line
@@ -407,6 +500,14 @@ impl Blocks {
 		self.blocks_list.pop();
 	}
 
+	// The syntax state at the start of the head block
+	fn head_state(&self) -> SyntaxState {
+		self.start_states
+			.get(&self.head_block)
+			.copied()
+			.unwrap_or_default()
+	}
+
 	// Remove the head Block
 	fn pop_head(&mut self) -> usize {
 		// Get the length of the first block
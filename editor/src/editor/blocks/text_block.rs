@@ -14,6 +14,8 @@ pub struct TextBlock {
 	pub block_num: usize,
 	// The text content of the current block
 	pub content: Vec<String>,
+	// Cached syntax highlighting of each line (None if the line needs to be highlighted)
+	pub highlights: Vec<Option<LineHighlight>>,
 	// Flag that tracks whether this block has been modified
 	pub is_modified: bool,
 	// Length of the Block (in lines)
@@ -127,7 +129,11 @@ impl TextBlock {
 		// If it doesn't end in a newline, fix the first line of this block
 		if !prev_newline {
 			// The starting index of the last line of the previous block
-			let last_line_start = prev_block_content.match_indices('\n').last().unwrap().0 + 1;
+			let last_line_start = prev_block_content
+				.match_indices('\n')
+				.next_back()
+				.unwrap()
+				.0 + 1;
 			// Construct a "complete" line
 			let line1 = String::from(&prev_block_content[last_line_start..]) + content[0].as_str();
 			// Set the first line of the block to this "fixed" first line
@@ -161,10 +167,12 @@ impl TextBlock {
 		let content = content
 			.into_par_iter()
 			.map(|line| String::from(line.trim_end_matches('\n')))
-			.collect();
+			.collect::<Vec<String>>();
 		// Create and return the block
 		let mut block = TextBlock {
 			block_num,
+			// None of the lines have been highlighted yet
+			highlights: vec![None; content.len()],
 			content,
 			// Can't be modified if new
 			is_modified: false,
@@ -176,6 +184,29 @@ impl TextBlock {
 		// Return the block
 		block
 	}
+
+	/* Highlight the lines of the block that aren't highlighted (or that start in
+	a different state than before) with the given highlighter, which is passed the
+	line number, text, and starting state of each line. Returns the state at the end
+	of the block. */
+	pub fn update_highlights(
+		&mut self,
+		starting_line_num: usize,
+		mut state: SyntaxState,
+		highlighter: &impl Fn(usize, &str, SyntaxState) -> LineHighlight,
+	) -> SyntaxState {
+		let lines = self.content.iter().zip(self.highlights.iter_mut());
+		for (idx, (line, highlight)) in lines.enumerate() {
+			// Only highlight the line if its cached highlighting is out of date
+			if !matches!(highlight, Some(highlight) if highlight.start_state == state) {
+				*highlight = Some(highlighter(starting_line_num + idx, line, state));
+			}
+			// The next line starts in the state this line ends in
+			state = highlight.as_ref().unwrap().end_state;
+		}
+
+		state
+	}
 }
 
 impl PartialEq for TextBlock {
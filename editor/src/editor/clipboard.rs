@@ -0,0 +1,159 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use cli_clipboard::{ClipboardContext, ClipboardProvider};
+use config::config::ClipboardPreference;
+use std::collections::{HashMap, VecDeque};
+
+// The maximum number of copied texts kept in the kill ring
+const KILL_RING_SIZE: usize = 30;
+
+// The clipboard that text is copied to and pasted from
+pub struct Clipboard {
+	// The system clipboard (if it's available and preferred)
+	system: Option<ClipboardContext>,
+	// Which clipboard is preferred
+	preference: ClipboardPreference,
+	// Every text that was copied (most recent first)
+	kill_ring: VecDeque<String>,
+	// Text yanked to the named registers (a-z)
+	registers: HashMap<char, String>,
+	// What the next letter key does with the named register
+	pub pending_register: Option<RegisterAction>,
+	// The kill ring index of the text pasted by the latest input
+	yank: Option<usize>,
+	// The paste made by the input before the latest input
+	last_yank: Option<usize>,
+	// The text of the latest copy if it was copied as a block (so it's pasted as a block)
+	block: Option<String>,
+	// An OSC 52 escape sequence that still needs to be written to the terminal
+	osc52: Option<String>,
+}
+
+// The actions that can be taken with a named register
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegisterAction {
+	// Copy text to the register
+	Yank,
+	// Paste the register's text
+	Paste,
+}
+
+impl Clipboard {
+	// Create a clipboard for the preferred provider
+	pub fn new(preference: ClipboardPreference) -> Self {
+		// Only connect to the system clipboard if it's preferred
+		let system = match preference {
+			ClipboardPreference::System => ClipboardContext::new().ok(),
+			_ => None,
+		};
+		Clipboard {
+			system,
+			preference,
+			kill_ring: VecDeque::new(),
+			registers: HashMap::new(),
+			pending_register: None,
+			yank: None,
+			last_yank: None,
+			block: None,
+			osc52: None,
+		}
+	}
+
+	// Copy text to the clipboard
+	pub fn set_contents(&mut self, text: String) {
+		// Try to copy to the system clipboard
+		let copied = match self.system.as_mut() {
+			Some(system) => system.set_contents(text.clone()).is_ok(),
+			None => false,
+		};
+		// Otherwise, copy to the terminal's clipboard (unless only the internal clipboard is used)
+		if !copied && self.preference != ClipboardPreference::Internal {
+			self.osc52 = Some(osc52_sequence(&text));
+		}
+		// Always keep the text for pasting
+		self.push_kill_ring(text);
+		self.block = None;
+	}
+
+	// Copy text that was selected as a block (e.g. with multiple cursors)
+	pub fn set_block_contents(&mut self, text: String) {
+		self.set_contents(text.clone());
+		self.block = Some(text);
+	}
+
+	// Check if the text was copied as a block
+	pub fn is_block(&self, text: &str) -> bool {
+		self.block.as_deref() == Some(text)
+	}
+
+	// Get the text to paste (from the system clipboard if possible)
+	pub fn get_contents(&mut self) -> String {
+		match self
+			.system
+			.as_mut()
+			.and_then(|system| system.get_contents().ok())
+		{
+			// Text copied outside of the editor is added to the kill ring
+			Some(text) => {
+				self.push_kill_ring(text.clone());
+				text
+			}
+			// Otherwise, paste the most recently copied text
+			None => self.kill_ring.front().cloned().unwrap_or_default(),
+		}
+	}
+
+	// Take the OSC 52 escape sequence that needs to be written to the terminal (if there is one)
+	pub fn take_osc52(&mut self) -> Option<String> {
+		self.osc52.take()
+	}
+
+	// Add text to the front of the kill ring
+	fn push_kill_ring(&mut self, text: String) {
+		// Don't record empty text or the same text twice in a row
+		if text.is_empty() || self.kill_ring.front() == Some(&text) {
+			return;
+		}
+		self.kill_ring.push_front(text);
+		self.kill_ring.truncate(KILL_RING_SIZE);
+	}
+
+	// Get the text in the kill ring at the given index (0 is the most recent)
+	pub fn kill_ring_entry(&self, index: usize) -> Option<String> {
+		self.kill_ring.get(index).cloned()
+	}
+
+	// The number of texts in the kill ring
+	pub fn kill_ring_len(&self) -> usize {
+		self.kill_ring.len()
+	}
+
+	// Yank text to a named register
+	pub fn set_register(&mut self, name: char, text: String) {
+		self.registers.insert(name, text);
+	}
+
+	// Get the text in a named register
+	pub fn get_register(&self, name: char) -> Option<String> {
+		self.registers.get(&name).cloned()
+	}
+
+	// Record that the latest input pasted the kill ring entry at the index
+	pub fn set_yank(&mut self, index: usize) {
+		self.yank = Some(index);
+	}
+
+	// Get the paste made by the previous input (if it was a paste)
+	pub fn last_yank(&self) -> Option<usize> {
+		self.last_yank
+	}
+
+	// Start a new input (cycling through the kill ring only continues from a paste)
+	pub fn start_input(&mut self) {
+		self.last_yank = self.yank.take();
+	}
+}
+
+// Create the OSC 52 escape sequence that copies the text to the terminal's clipboard
+pub fn osc52_sequence(text: &str) -> String {
+	format!("\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
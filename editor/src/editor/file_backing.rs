@@ -0,0 +1,107 @@
+use std::{
+	fs::File,
+	io::{stdin, Cursor, Error, Read, Seek, SeekFrom},
+};
+
+// Detection and (de)compression of compressed files
+mod compression;
+pub use compression::Compression;
+
+// The source that the TextBlocks of the editor are read from
+#[derive(Debug)]
+pub enum FileBacking {
+	// A named, seekable file on disk
+	Disk(File),
+	// An in-memory buffer (used for text piped in through stdin or decompressed files)
+	Memory(Cursor<Vec<u8>>),
+}
+
+impl FileBacking {
+	// Read all of stdin into an in-memory buffer
+	pub fn from_stdin() -> Result<Self, Error> {
+		// Buffer to read stdin into
+		let mut buffer = Vec::new();
+		// Read until EOF (keyboard input is read from the controlling terminal instead)
+		stdin().read_to_end(&mut buffer)?;
+
+		Ok(Self::from_bytes(buffer))
+	}
+
+	/* Create a backing from a file on disk. If the file is compressed, it is
+	decompressed into an in-memory buffer and its compression format is returned. */
+	pub fn from_file(mut file: File) -> Result<(Self, Option<Compression>), Error> {
+		// Read the magic bytes at the start of the file
+		let mut magic = [0; 4];
+		let num_bytes = file.read(&mut magic)?;
+		// Move back to the start of the file
+		file.seek(SeekFrom::Start(0))?;
+
+		match Compression::detect(&magic[..num_bytes]) {
+			// Decompress the file into an in-memory buffer
+			Some(compression) => {
+				let mut bytes = Vec::new();
+				file.read_to_end(&mut bytes)?;
+				let bytes = compression.decompress(&bytes)?;
+				Ok((Self::from_bytes(bytes), Some(compression)))
+			}
+			// Otherwise, read the file directly
+			None => Ok((FileBacking::Disk(file), None)),
+		}
+	}
+
+	// Create an in-memory backing from the given bytes
+	pub fn from_bytes(bytes: Vec<u8>) -> Self {
+		FileBacking::Memory(Cursor::new(bytes))
+	}
+
+	// Ensure that the data (and metadata) of a file on disk is up to date
+	pub fn sync_all(&self) -> Result<(), Error> {
+		match self {
+			FileBacking::Disk(file) => file.sync_all(),
+			// Nothing to sync for an in-memory buffer
+			FileBacking::Memory(_) => Ok(()),
+		}
+	}
+
+	// Get the number of bytes in the backing
+	pub fn size(&self) -> Result<u64, Error> {
+		match self {
+			FileBacking::Disk(file) => Ok(file.metadata()?.len()),
+			FileBacking::Memory(buffer) => Ok(buffer.get_ref().len() as u64),
+		}
+	}
+
+	// Read the entire contents of the backing
+	pub fn contents(&mut self) -> Result<Vec<u8>, Error> {
+		// Buffer to read the contents into
+		let mut buffer = Vec::new();
+		// Move to the start of the backing and read everything
+		self.seek(SeekFrom::Start(0))?;
+		self.read_to_end(&mut buffer)?;
+
+		Ok(buffer)
+	}
+
+	// Check if the backing is an in-memory buffer rather than a file on disk
+	pub fn is_in_memory(&self) -> bool {
+		matches!(self, FileBacking::Memory(_))
+	}
+}
+
+impl Read for FileBacking {
+	fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+		match self {
+			FileBacking::Disk(file) => file.read(buf),
+			FileBacking::Memory(buffer) => buffer.read(buf),
+		}
+	}
+}
+
+impl Seek for FileBacking {
+	fn seek(&mut self, pos: SeekFrom) -> Result<u64, Error> {
+		match self {
+			FileBacking::Disk(file) => file.seek(pos),
+			FileBacking::Memory(buffer) => buffer.seek(pos),
+		}
+	}
+}
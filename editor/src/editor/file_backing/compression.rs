@@ -0,0 +1,56 @@
+use std::io::{Error, Read, Write};
+
+use flate2::{read::MultiGzDecoder, write::GzEncoder};
+
+// The magic bytes at the start of a gzip file
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+// The magic bytes at the start of a zstd file
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+// The compression format of a compressed file
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compression {
+	Gzip,
+	Zstd,
+}
+
+impl Compression {
+	// Detect the compression format of the given bytes from their magic bytes
+	pub fn detect(bytes: &[u8]) -> Option<Self> {
+		if bytes.starts_with(&GZIP_MAGIC) {
+			Some(Compression::Gzip)
+		} else if bytes.starts_with(&ZSTD_MAGIC) {
+			Some(Compression::Zstd)
+		} else {
+			None
+		}
+	}
+
+	// Decompress the given bytes
+	pub fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>, Error> {
+		match self {
+			Compression::Gzip => {
+				// Buffer to decompress into
+				let mut buffer = Vec::new();
+				// Decompress every gzip member in the file
+				MultiGzDecoder::new(bytes).read_to_end(&mut buffer)?;
+				Ok(buffer)
+			}
+			Compression::Zstd => zstd::stream::decode_all(bytes),
+		}
+	}
+
+	// Compress the given bytes
+	pub fn compress(&self, bytes: &[u8]) -> Result<Vec<u8>, Error> {
+		match self {
+			Compression::Gzip => {
+				// Compress with the default compression level
+				let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+				encoder.write_all(bytes)?;
+				encoder.finish()
+			}
+			// Level 0 uses zstd's default compression level
+			Compression::Zstd => zstd::stream::encode_all(bytes, 0),
+		}
+	}
+}
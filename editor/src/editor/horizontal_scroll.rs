@@ -0,0 +1,94 @@
+use ratatui::text::{Line, Span};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+// Shown in the first column of the widget if a line has text to the left of the widget
+const LEFT_INDICATOR: &str = "«";
+// Shown in the last column of the widget if a line has text to the right of the widget
+const RIGHT_INDICATOR: &str = "»";
+
+/* Clip a rendered line to the columns that are visible in the widget.
+The offset is the number of display columns scrolled to the right. If there is
+text clipped on either side, an indicator replaces the column on that side. */
+pub fn clip_line(line: Line<'_>, offset: usize, width: usize) -> Line<'_> {
+	// Style of the entire line
+	let style = line.style;
+	// The display width of the entire line
+	let line_width = line.width();
+	// Flags for whether text is clipped on either side of the widget
+	let left_clipped = offset > 0 && line_width > 0;
+	let right_clipped = line_width > offset + width;
+
+	// The visible spans of the line
+	let mut spans = Vec::new();
+	// The display column of the current grapheme
+	let mut column = 0;
+	for span in line.spans {
+		// The visible text of the span
+		let mut text = String::new();
+		for grapheme in span.content.graphemes(true) {
+			// The columns the grapheme starts and ends at
+			let (start, end) = (column, column + UnicodeWidthStr::width(grapheme));
+			column = end;
+			// Skip graphemes that are outside of the widget
+			if end <= offset || start >= offset + width {
+				continue;
+			}
+
+			// Check if the grapheme is partially hidden or covered by an indicator
+			if start < offset
+				|| end > offset + width
+				|| (left_clipped && start == offset)
+				|| (right_clipped && end == offset + width)
+			{
+				// Fill each visible column of the grapheme with a space or an indicator
+				for col in start.max(offset)..end.min(offset + width) {
+					text.push_str(indicator(col, offset, width, left_clipped, right_clipped));
+				}
+			// Otherwise, the grapheme is fully visible
+			} else {
+				text.push_str(grapheme);
+			}
+		}
+		spans.push(Span::styled(text, span.style));
+	}
+
+	// If all of the text is to the left of the widget, only show the indicator
+	if left_clipped && line_width <= offset {
+		spans.push(Span::from(LEFT_INDICATOR));
+	}
+
+	Line::from(spans).style(style)
+}
+
+// Get the text to display in the given column of a clipped grapheme
+fn indicator(
+	column: usize,
+	offset: usize,
+	width: usize,
+	left_clipped: bool,
+	right_clipped: bool,
+) -> &'static str {
+	if left_clipped && column == offset {
+		LEFT_INDICATOR
+	} else if right_clipped && column == offset + width - 1 {
+		RIGHT_INDICATOR
+	} else {
+		" "
+	}
+}
+
+/* Get the new horizontal offset so that the cursor's column is visible.
+The cursor is kept off of the columns used by the indicators. */
+pub fn follow_cursor(offset: usize, cursor_column: usize, width: usize) -> usize {
+	// If the cursor is left of the widget (or on the left indicator)
+	if cursor_column < offset || (offset > 0 && cursor_column == offset) {
+		cursor_column.saturating_sub(1)
+	// If the cursor is right of the widget (or on the right indicator)
+	} else if width > 1 && cursor_column + 1 >= offset + width {
+		cursor_column + 2 - width
+	// Otherwise, the cursor is already visible
+	} else {
+		offset
+	}
+}
@@ -1,21 +1,38 @@
 use super::{
-	copy_paste, editing_keys, highlight_keys, key_functions, navigation_keys, save_key,
-	EditorSpace, KeyCode, StackChoice,
+	block_selection, bracket_match, comment_keys, copy_paste, editing_keys, highlight_keys, indent,
+	key_functions, line_keys, mouse, multi_cursor, navigation_keys, save_key, select_keys,
+	EditorSpace, KeyCode, MouseButton, MouseEvent, MouseEventKind, RegisterAction, StackChoice,
 };
 
 pub fn no_modifiers(editor: &mut EditorSpace, code: KeyCode) {
+	// Moving the cursor removes the extra cursors
+	clear_cursors(editor, code);
 	// Return the key
 	match code {
-		// If normal character, insert that character
-		KeyCode::Char(code) => editing_keys::char_key(editor, code),
+		// If normal character, insert that character (at every cursor)
+		KeyCode::Char(code) => {
+			multi_cursor::edit(editor, false, |editor| editing_keys::char_key(editor, code))
+		}
 		// If Enter was pressed, insert newline
-		KeyCode::Enter => editing_keys::enter_key(editor),
+		KeyCode::Enter => multi_cursor::edit(editor, true, editing_keys::enter_key),
+		// Tab indents every line of a multiline selection
+		KeyCode::Tab if indent::is_multiline_selection(editor) => {
+			indent::indent_lines(editor, false)
+		}
 		// If tab was pressed, insert tab character
-		KeyCode::Tab => editing_keys::tab_key(editor),
+		KeyCode::Tab => multi_cursor::edit(editor, false, editing_keys::tab_key),
+		// Shift + Tab outdents the selected lines (or the cursor's line)
+		KeyCode::BackTab => indent::indent_lines(editor, true),
+		// Backspace and delete remove the text of a block selection
+		KeyCode::Backspace | KeyCode::Delete if block_selection::is_selecting(editor) => {
+			block_selection::delete(editor)
+		}
 		// If backspace was pressed, remove the previous character
-		KeyCode::Backspace => editing_keys::backspace(editor),
+		KeyCode::Backspace => multi_cursor::edit(editor, false, editing_keys::backspace),
 		// If delete was pressed, remove the next character
-		KeyCode::Delete => editing_keys::delete_key(editor),
+		KeyCode::Delete => multi_cursor::edit(editor, false, editing_keys::delete_key),
+		// Escape removes the extra cursors
+		KeyCode::Esc => multi_cursor::clear(editor),
 		// Left arrow moves cursor left
 		KeyCode::Left => {
 			// Clear the highlighted selection of text
@@ -77,9 +94,13 @@ pub fn no_modifiers(editor: &mut EditorSpace, code: KeyCode) {
 }
 
 pub fn shift_modifier(editor: &mut EditorSpace, code: KeyCode) {
+	// Highlighting removes the extra cursors
+	clear_cursors(editor, code);
 	match code {
 		// Uppercase characters
-		KeyCode::Char(code) => editing_keys::char_key(editor, code.to_ascii_uppercase()),
+		KeyCode::Char(code) => multi_cursor::edit(editor, false, |editor| {
+			editing_keys::char_key(editor, code.to_ascii_uppercase())
+		}),
 		// Right arrow highlight text to the right
 		KeyCode::Right => highlight_keys::highlight_right(editor),
 		// Left arrow highlight text to the left
@@ -96,11 +117,15 @@ pub fn shift_modifier(editor: &mut EditorSpace, code: KeyCode) {
 		KeyCode::PageUp => highlight_keys::highlight_page_up(editor),
 		// Highlight one page down
 		KeyCode::PageDown => highlight_keys::highlight_page_down(editor),
+		// Outdent the selected lines (or the cursor's line)
+		KeyCode::BackTab => indent::indent_lines(editor, true),
 		_ => (),
 	}
 }
 
 pub fn control_modifier(editor: &mut EditorSpace, code: KeyCode, break_loop: &mut bool) {
+	// Jumping removes the extra cursors
+	clear_cursors(editor, code);
 	match code {
 		// Save the frame to the file
 		KeyCode::Char('s') => save_key::save_key_combo(editor, false, ""),
@@ -108,25 +133,44 @@ pub fn control_modifier(editor: &mut EditorSpace, code: KeyCode, break_loop: &mu
 		KeyCode::Char('q') => *break_loop = true,
 		// Paste text into the editor
 		KeyCode::Char('v') => {
-			// If a clipboard exists
-			if editor.clipboard.is_some() {
-				copy_paste::paste_from_clipboard(editor);
-			}
+			copy_paste::paste_from_clipboard(editor);
 		}
 		// Copy text from the editor and write it to the clipboard
 		KeyCode::Char('c') => {
-			// If a clipboard exists
-			if editor.clipboard.is_some() {
-				copy_paste::copy_to_clipboard(editor);
-			}
+			copy_paste::copy_to_clipboard(editor);
 		}
 		// Cut text (copy and delete a selection)
 		KeyCode::Char('x') => {
-			// If a clipboard exists
-			if editor.clipboard.is_some() {
-				copy_paste::cut(editor);
-			}
+			copy_paste::cut(editor);
+		}
+		// Yank text to the named register typed next
+		KeyCode::Char('y') => {
+			editor.clipboard.pending_register = Some(RegisterAction::Yank);
+			editor.set_status_message("Yank to register (a-z)");
+		}
+		// Paste text from the named register typed next
+		KeyCode::Char('p') => {
+			editor.clipboard.pending_register = Some(RegisterAction::Paste);
+			editor.set_status_message("Paste from register (a-z)");
 		}
+		// Add a cursor at the next occurrence of the selected text
+		KeyCode::Char('d') => multi_cursor::add_next_occurrence(editor),
+		// Select the entire file
+		KeyCode::Char('a') => select_keys::select_all(editor),
+		// Select the word at the cursor
+		KeyCode::Char('w') => select_keys::select_word(editor),
+		// Select the line at the cursor
+		KeyCode::Char('l') => select_keys::select_line(editor),
+		// Delete the selected lines (or the cursor's line)
+		KeyCode::Char('k') => line_keys::delete_lines(editor),
+		// Toggle line comments
+		KeyCode::Char('/') => comment_keys::toggle_line_comment(editor),
+		// Terminals without keyboard enhancements send Ctrl+/ as Ctrl+7
+		KeyCode::Char('7') if editor.legacy_keys => comment_keys::toggle_line_comment(editor),
+		// Jump to the bracket matching the one at the cursor
+		KeyCode::Char(']') => bracket_match::jump_to_match(editor),
+		// Terminals without keyboard enhancements send Ctrl+] as Ctrl+5
+		KeyCode::Char('5') if editor.legacy_keys => bracket_match::jump_to_match(editor),
 		// Undo a change
 		KeyCode::Char('z') => {
 			key_functions::undo_redo(editor, StackChoice::Undo);
@@ -168,6 +212,8 @@ pub fn control_modifier(editor: &mut EditorSpace, code: KeyCode, break_loop: &mu
 }
 
 pub fn control_and_shift_modifiers(editor: &mut EditorSpace, code: KeyCode) {
+	// Highlighting removes the extra cursors
+	clear_cursors(editor, code);
 	match code {
 		// Highlight the entire unicode word to the right
 		KeyCode::Right => navigation_keys::jump_right(editor, true),
@@ -180,3 +226,104 @@ pub fn control_and_shift_modifiers(editor: &mut EditorSpace, code: KeyCode) {
 		_ => (),
 	}
 }
+
+pub fn control_and_alt_modifiers(editor: &mut EditorSpace, code: KeyCode) {
+	match code {
+		// Add a cursor on the line above the top cursor
+		KeyCode::Up => multi_cursor::add_cursor_above(editor),
+		// Add a cursor on the line below the bottom cursor
+		KeyCode::Down => multi_cursor::add_cursor_below(editor),
+		_ => (),
+	}
+}
+
+pub fn alt_and_shift_modifiers(editor: &mut EditorSpace, code: KeyCode) {
+	match code {
+		// Extend the block selection by one column or line
+		KeyCode::Left => block_selection::block_left(editor),
+		KeyCode::Right => block_selection::block_right(editor),
+		KeyCode::Up => block_selection::block_up(editor),
+		KeyCode::Down => block_selection::block_down(editor),
+		_ => (),
+	}
+}
+
+pub fn mouse_input(editor: &mut EditorSpace, event: MouseEvent) {
+	// Clicking and dragging end cycling through the kill ring
+	if matches!(
+		event.kind,
+		MouseEventKind::Down(_) | MouseEventKind::Drag(_)
+	) {
+		editor.clipboard.start_input();
+	}
+	match event.kind {
+		// Clicking moves the cursor (or selects a word or line) and removes the extra cursors
+		MouseEventKind::Down(MouseButton::Left) => {
+			multi_cursor::clear(editor);
+			mouse::click(editor, event.column, event.row);
+		}
+		// Dragging highlights text
+		MouseEventKind::Drag(MouseButton::Left) => mouse::drag(editor, event.column, event.row),
+		// The mouse wheel scrolls the text
+		MouseEventKind::ScrollUp => mouse::scroll_up(editor),
+		MouseEventKind::ScrollDown => mouse::scroll_down(editor),
+		_ => (),
+	}
+}
+
+pub fn alt_modifier(editor: &mut EditorSpace, code: KeyCode) {
+	match code {
+		// Toggle soft wrapping of long lines
+		KeyCode::Char('z') => key_functions::toggle_soft_wrap(editor),
+		// Replace the text that was just pasted with older text from the kill ring
+		KeyCode::Char('v') => copy_paste::paste_previous(editor),
+		// Add a cursor at every occurrence of the selected text
+		KeyCode::Char('d') => multi_cursor::add_all_occurrences(editor),
+		// Expand the selection to the enclosing word, quotes, brackets, line, paragraph, or file
+		KeyCode::Right => select_keys::expand_selection(editor),
+		// Shrink the selection back to what it was before it was expanded
+		KeyCode::Left => select_keys::shrink_selection(editor),
+		// Convert the indentation of the selection (or file) between tabs and spaces
+		KeyCode::Char('i') => indent::convert_indentation(editor),
+		// Move the selected lines (or the cursor's line) up or down
+		KeyCode::Up => line_keys::move_lines_up(editor),
+		KeyCode::Down => line_keys::move_lines_down(editor),
+		// Duplicate the selected lines (or the cursor's line)
+		KeyCode::Char('c') => line_keys::duplicate_lines(editor),
+		// Join the next line (or the selected lines) onto the cursor's line
+		KeyCode::Char('j') => line_keys::join_lines(editor),
+		// Toggle a block comment around the selection (or the cursor's line)
+		KeyCode::Char('/') => comment_keys::toggle_block_comment(editor),
+		_ => (),
+	}
+}
+
+// Yank to or paste from the register named by the key
+pub fn register_key(editor: &mut EditorSpace, action: RegisterAction, code: KeyCode) {
+	match code {
+		// Registers are named by the letters a-z
+		KeyCode::Char(name) if name.is_ascii_lowercase() => match action {
+			RegisterAction::Yank => copy_paste::yank_to_register(editor, name),
+			RegisterAction::Paste => copy_paste::paste_from_register(editor, name),
+		},
+		// Any other key cancels
+		_ => editor.set_status_message("Register cancelled"),
+	}
+}
+
+// Remove the extra cursors if the key moves the cursor
+fn clear_cursors(editor: &mut EditorSpace, code: KeyCode) {
+	if matches!(
+		code,
+		KeyCode::Left
+			| KeyCode::Right
+			| KeyCode::Up
+			| KeyCode::Down
+			| KeyCode::Home
+			| KeyCode::End
+			| KeyCode::PageUp
+			| KeyCode::PageDown
+	) {
+		multi_cursor::clear(editor);
+	}
+}
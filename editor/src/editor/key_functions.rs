@@ -2,9 +2,9 @@
 // Contains the logic for all the keys pressed
 
 use super::{
-	blocks::Blocks, ClipboardProvider, EditorSpace, File, IndexedParallelIterator,
-	IntoParallelIterator, OpenOptions, ParallelExtend, ParallelIterator, StackChoice,
-	UnicodeSegmentation,
+	blocks::Blocks, soft_wrap, AutoIndent, Compression, Config, EditorSpace, File, FileBacking,
+	IntoParallelIterator, OpenOptions, ParallelExtend, ParallelIterator, StackChoice, Token,
+	TokenKind, UnRedoStack, UnicodeSegmentation,
 };
 
 use unicode_segmentation::GraphemeCursor;
@@ -20,6 +20,24 @@ pub mod navigation_keys;
 pub mod save_key;
 // Contains the copy/paste logic
 pub mod copy_paste;
+// Contains clicking, dragging, and scrolling with the mouse
+pub mod mouse;
+// Contains editing with multiple cursors
+pub mod multi_cursor;
+// Contains selecting a rectangular block of text
+pub mod block_selection;
+// Contains selecting all, a word, or a line and expanding the selection
+pub mod select_keys;
+// Contains automatically indenting new lines
+pub mod indent;
+// Contains moving, duplicating, joining, and deleting lines
+pub mod line_keys;
+// Contains commenting and uncommenting lines
+pub mod comment_keys;
+// Contains automatically closing brackets and quotes
+pub mod auto_pair;
+// Contains matching brackets (and jumping between them)
+pub mod bracket_match;
 
 // Check the beginning of line cursor condition
 fn check_cursor_begin_line(editor: &mut EditorSpace) -> bool {
@@ -31,12 +49,27 @@ fn check_cursor_begin_line(editor: &mut EditorSpace) -> bool {
 pub fn check_cursor_end_line(editor: &mut EditorSpace) -> bool {
 	// The line of text
 	let line = editor.blocks.as_ref().unwrap().get_current_line();
-	// Get the number of tabs on the line
-	let num_tabs = line.matches('\t').count();
 	// If the cursor is beyond the end of the line, return false
-	editor.cursor_position[0]
-		< UnicodeWidthStr::width(line.as_str()) + num_tabs * (editor.config.tab_width - 1)
-		&& editor.cursor_position[0] < editor.width
+	editor.cursor_position[0] < display_width(&line, editor.config.tab_width)
+}
+
+// Get the number of columns that text is displayed in (with tabs expanded to tab_width)
+pub fn display_width(text: &str, tab_width: usize) -> usize {
+	// Get the number of tabs in the text
+	let num_tabs = text.matches('\t').count();
+	UnicodeWidthStr::width(text) + num_tabs * (tab_width - 1)
+}
+
+// Turn soft wrapping of long lines on or off
+pub fn toggle_soft_wrap(editor: &mut EditorSpace) {
+	editor.config.soft_wrap = !editor.config.soft_wrap;
+	// Soft wrapped lines are never scrolled horizontally
+	editor.horizontal_offset = 0;
+	// Tell the user whether soft wrapping is on
+	match editor.config.soft_wrap {
+		true => editor.set_status_message("Soft wrap on"),
+		false => editor.set_status_message("Soft wrap off"),
+	}
 }
 
 // Calls the UnRedoStack undo or redo and sets the editor's state
@@ -58,4 +91,7 @@ pub fn undo_redo(editor: &mut EditorSpace, stack_choice: StackChoice) {
 	editor.scroll_offset = state.3;
 	editor.blocks = Some(state.4);
 	editor.selection = state.5;
+	editor.file_length = state.6;
+	// The extra cursors aren't part of the undo state
+	multi_cursor::clear(editor);
 }
@@ -0,0 +1,108 @@
+// Contains the logic for automatically closing brackets and quotes
+
+use super::{highlight_keys, line_keys, navigation_keys, EditorSpace};
+
+// The opening and closing characters of each pair
+const PAIRS: [(char, char); 5] = [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')];
+
+// Wrap the selection in the pair opened by the character (returns false if it isn't an opener)
+pub fn surround_selection(editor: &mut EditorSpace, code: char) -> bool {
+	let Some(close) = closer(editor, code) else {
+		return false;
+	};
+	if editor.selection.is_empty {
+		return false;
+	}
+	let (anchor, cursor) = highlight_keys::selection_ends(editor);
+	let (start, end) = (editor.selection.start, editor.selection.end);
+	// Add a new undo state for the surrounded selection
+	editor.update_unredo_stack(true);
+
+	// Insert the closer after the selection first (so the start of the selection doesn't move)
+	let mut last = line_keys::read_lines(editor, end[1], end[1]).remove(0);
+	last.insert(end[0], close);
+	line_keys::update_line(editor, last, end[1]);
+	let mut first = line_keys::read_lines(editor, start[1], start[1]).remove(0);
+	first.insert(start[0], code);
+	line_keys::update_line(editor, first, start[1]);
+
+	// Keep the same text selected (inside of the pair)
+	let shift = |[position, line_num]: [usize; 2]| match line_num == start[1] {
+		true => [position + code.len_utf8(), line_num],
+		false => [position, line_num],
+	};
+	highlight_keys::select_range(editor, shift(anchor), shift(cursor));
+	true
+}
+
+// Move over the closer in front of the cursor instead of typing another one
+pub fn skip_closer(editor: &mut EditorSpace, code: char) -> bool {
+	let is_closer = PAIRS.iter().any(|(_, close)| *close == code);
+	if !editor.config.auto_pair || !is_closer || neighbors(editor).1 != Some(code) {
+		return false;
+	}
+	navigation_keys::right_arrow(editor, true);
+	true
+}
+
+// Insert the closer of the pair that was just opened (after the cursor)
+pub fn insert_closer(editor: &mut EditorSpace, code: char) {
+	let Some(close) = closer(editor, code) else {
+		return;
+	};
+	// The characters before the opener and after the cursor
+	let line = editor.blocks.as_ref().unwrap().get_current_line();
+	let before = line[..editor.text_position - code.len_utf8()]
+		.chars()
+		.next_back();
+	let after = line[editor.text_position..].chars().next();
+	// Only close a pair that isn't followed by text
+	let is_open = match after {
+		Some(ch) => ch.is_whitespace() || PAIRS.iter().any(|(_, close)| *close == ch),
+		None => true,
+	};
+	// Quotes after a word (e.g. an apostrophe) aren't closed
+	let is_quote = code == close;
+	if !is_open || (is_quote && before.is_some_and(|ch| ch.is_alphanumeric() || ch == code)) {
+		return;
+	}
+	editor
+		.blocks
+		.as_mut()
+		.unwrap()
+		.insert_char_in_line(editor.text_position, close);
+}
+
+// Check if the cursor is between an empty pair (which backspace deletes together)
+pub fn in_empty_pair(editor: &EditorSpace) -> bool {
+	match neighbors(editor) {
+		(Some(open), Some(close)) => closer(editor, open) == Some(close),
+		_ => false,
+	}
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+// Get the closer of the pair opened by a character (if auto pairing is enabled)
+fn closer(editor: &EditorSpace, code: char) -> Option<char> {
+	if !editor.config.auto_pair {
+		return None;
+	}
+	PAIRS
+		.iter()
+		.find(|(open, _)| *open == code)
+		.map(|(_, close)| *close)
+}
+
+// Get the characters before and after the cursor
+fn neighbors(editor: &EditorSpace) -> (Option<char>, Option<char>) {
+	let line = editor.blocks.as_ref().unwrap().get_current_line();
+	(
+		line[..editor.text_position].chars().next_back(),
+		line[editor.text_position..].chars().next(),
+	)
+}
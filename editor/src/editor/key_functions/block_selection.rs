@@ -0,0 +1,212 @@
+// Contains the logic for selecting (and editing) a rectangular block of text
+
+use super::{
+	check_cursor_end_line,
+	copy_paste::{self, paste_subroutines},
+	editing_keys,
+	multi_cursor::{self, Cursor},
+	navigation_keys, EditorSpace, UnRedoStack,
+};
+
+// A rectangle of text selected by display column (one cursor selects each of its rows)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockSelection {
+	// The corner where the selection started (as [display column, line number])
+	pub anchor: [usize; 2],
+	// The corner that is moved by the cursor (as [display column, line number])
+	pub corner: [usize; 2],
+}
+
+// Extend the block selection one column to the left
+pub fn block_left(editor: &mut EditorSpace) {
+	let mut block = current_block(editor);
+	block.corner[0] = block.corner[0].saturating_sub(1);
+	select_block(editor, block);
+}
+
+// Extend the block selection one column to the right
+pub fn block_right(editor: &mut EditorSpace) {
+	let mut block = current_block(editor);
+	block.corner[0] += 1;
+	select_block(editor, block);
+}
+
+// Extend the block selection up one line
+pub fn block_up(editor: &mut EditorSpace) {
+	let mut block = current_block(editor);
+	block.corner[1] = block.corner[1].saturating_sub(1);
+	select_block(editor, block);
+}
+
+// Extend the block selection down one line
+pub fn block_down(editor: &mut EditorSpace) {
+	let mut block = current_block(editor);
+	block.corner[1] = (block.corner[1] + 1).min(editor.file_length - 1);
+	select_block(editor, block);
+}
+
+// Check if a block selection has any text selected
+pub fn is_selecting(editor: &EditorSpace) -> bool {
+	editor.block_selection.is_some()
+		&& (!editor.selection.is_empty
+			|| editor
+				.cursors
+				.iter()
+				.any(|cursor| !cursor.selection.is_empty))
+}
+
+// Get the text selected by every cursor (one line for each cursor, from the top of the file down)
+pub fn copy_text(editor: &mut EditorSpace) -> String {
+	// Every cursor (in order from the top of the file down)
+	let mut cursors = vec![Cursor::primary(editor)];
+	cursors.extend(editor.cursors.iter().cloned());
+	cursors.sort_by_key(Cursor::start);
+
+	cursors
+		.iter()
+		.map(|cursor| match cursor.selection.is_empty {
+			true => String::new(),
+			false => selected_text(editor, cursor),
+		})
+		.collect::<Vec<String>>()
+		.join("\n")
+}
+
+// Delete the text selected by every cursor (as a single undo state)
+pub fn delete(editor: &mut EditorSpace) {
+	multi_cursor::edit(editor, true, |editor| {
+		// Rows of a block that are shorter than the block have nothing to delete
+		if !editor.selection.is_empty {
+			editor.delete_selection();
+		}
+	});
+}
+
+/* Paste text that was copied as a block. With one cursor for each of its lines, each line is
+pasted at a cursor. Otherwise, its lines are pasted on the rows below the cursor (at its column). */
+pub fn paste_block(editor: &mut EditorSpace, text: &str) {
+	// The lines of the block
+	let lines = paste_subroutines::split_text(text);
+	// Paste each line at a cursor
+	if editor.cursors.len() + 1 == lines.len() {
+		multi_cursor::edit_each(editor, true, |editor, idx| {
+			copy_paste::paste_text(editor, &lines[idx])
+		});
+		return;
+	}
+	// Paste the entire text at every cursor
+	if !editor.cursors.is_empty() {
+		multi_cursor::edit(editor, true, |editor| copy_paste::paste_text(editor, text));
+		return;
+	}
+
+	// Add a new undo state for the entire paste
+	editor.update_unredo_stack(true);
+	// Don't add undo states while preparing the rows
+	let unredo_stack = std::mem::replace(&mut editor.unredo_stack, UnRedoStack::paused());
+	// Delete the selection to paste over
+	if !editor.selection.is_empty {
+		editor.delete_selection();
+	}
+
+	// The position of the cursor (where the top left corner of the block is pasted)
+	let line_num = editor.get_line_num(editor.cursor_position[1]);
+	let column = editor.cursor_position[0];
+	// Place a cursor at the column of each row
+	let mut cursors = Vec::new();
+	for row in line_num..line_num + lines.len() {
+		// Add lines after the end of the file (if the block doesn't fit)
+		if row >= editor.file_length {
+			navigation_keys::jump_to(editor, editor.file_length - 1, usize::MAX);
+			editing_keys::new_line(editor);
+		}
+		navigation_keys::jump_to(editor, row, 0);
+		navigation_keys::move_to_column(editor, column, None);
+		// Pad rows that are shorter than the column with spaces
+		while editor.cursor_position[0] < column && !check_cursor_end_line(editor) {
+			editing_keys::char_key(editor, ' ');
+		}
+		cursors.push(Cursor::new(row, editor.text_position));
+	}
+
+	// Paste each line of the block at its row
+	multi_cursor::activate(editor, &cursors[0]);
+	editor.cursors = cursors.split_off(1);
+	multi_cursor::edit_each(editor, true, |editor, idx| {
+		copy_paste::paste_text(editor, &lines[idx])
+	});
+	editor.unredo_stack = unredo_stack;
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+// Get the current block selection (or start a new one at the cursor)
+fn current_block(editor: &EditorSpace) -> BlockSelection {
+	editor.block_selection.unwrap_or_else(|| {
+		// The display column and line of the cursor
+		let position = [
+			editor.cursor_position[0],
+			editor.get_line_num(editor.cursor_position[1]),
+		];
+		BlockSelection {
+			anchor: position,
+			corner: position,
+		}
+	})
+}
+
+// Select the block with a cursor on each of its rows
+fn select_block(editor: &mut EditorSpace, block: BlockSelection) {
+	// The first and last rows and columns of the block
+	let (top, bottom) = min_max(block.anchor[1], block.corner[1]);
+	let (left, right) = min_max(block.anchor[0], block.corner[0]);
+
+	// Visit each row to find the text between the columns
+	editor.selection.is_empty = true;
+	let mut cursors = Vec::new();
+	for row in top..=bottom {
+		navigation_keys::jump_to(editor, row, 0);
+		navigation_keys::move_to_column(editor, left, None);
+		let start = editor.text_position;
+		navigation_keys::move_to_column(editor, right, None);
+		let end = editor.text_position;
+		cursors.push(match start < end {
+			true => Cursor::selecting(row, start, end),
+			false => Cursor::new(row, start),
+		});
+	}
+
+	// The cursor on the row of the moving corner is the primary cursor
+	let primary = cursors.remove(block.corner[1] - top);
+	multi_cursor::activate(editor, &primary);
+	editor.stored_position = block.corner[0];
+	editor.cursors = cursors;
+	editor.block_selection = Some(block);
+}
+
+// Get the text selected by a cursor
+fn selected_text(editor: &mut EditorSpace, cursor: &Cursor) -> String {
+	let [start, end] = [cursor.selection.start, cursor.selection.end];
+	// Selections on a single line are sliced from the line
+	if start[1] == end[1] {
+		let line = editor
+			.blocks
+			.as_ref()
+			.unwrap()
+			.get_some_line(start[1])
+			.unwrap_or_default();
+		return String::from(line.get(start[0]..end[0]).unwrap_or_default());
+	}
+	// Otherwise, copy the selection like a regular copy
+	multi_cursor::activate(editor, cursor);
+	copy_paste::copy_text(editor)
+}
+
+// Order two values
+fn min_max(a: usize, b: usize) -> (usize, usize) {
+	(a.min(b), a.max(b))
+}
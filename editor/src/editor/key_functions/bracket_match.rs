@@ -0,0 +1,211 @@
+// Contains the logic for finding (and jumping to) the bracket that matches the one at the cursor
+
+use super::{navigation_keys, Blocks, EditorSpace, Token, TokenKind};
+
+// The pairs of brackets that can be matched
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+// The result of searching the loaded blocks for the matching bracket
+enum Search {
+	// The [text position, line number] of the matching bracket
+	Found([usize; 2]),
+	// There is no matching bracket in the file
+	Unmatched,
+	// The search reached the end (if true) or the start of the loaded blocks
+	Unloaded(bool),
+}
+
+/* Get the [text position, line number] of the bracket at the cursor and its match
+(if it's in the loaded blocks). These are highlighted when the blocks are displayed. */
+pub fn matching_brackets(editor: &EditorSpace, blocks: &Blocks) -> Vec<[usize; 2]> {
+	let line_num = editor.get_line_num(editor.cursor_position[1]);
+	let Some(bracket) = bracket_at(blocks, [editor.text_position, line_num]) else {
+		return Vec::new();
+	};
+	match search(blocks, bracket) {
+		Search::Found(found) => vec![bracket, found],
+		_ => Vec::new(),
+	}
+}
+
+// Move the cursor to the bracket that matches the one at the cursor
+pub fn jump_to_match(editor: &mut EditorSpace) {
+	// Search a copy of the blocks (which loads in more blocks as needed)
+	let mut blocks = editor.blocks.as_ref().unwrap().clone();
+	editor.update_highlights(&mut blocks);
+	let line_num = editor.get_line_num(editor.cursor_position[1]);
+	let Some(bracket) = bracket_at(&blocks, [editor.text_position, line_num]) else {
+		editor.set_status_message("No bracket at the cursor");
+		return;
+	};
+
+	// Load in the next (or previous) block until the match is found
+	let found = loop {
+		match search(&blocks, bracket) {
+			Search::Found(found) => break Some(found),
+			Search::Unmatched => break None,
+			Search::Unloaded(true) => {
+				blocks
+					.push_tail(editor, false)
+					.unwrap_or_else(|err| panic!("Couldn't load the next block | {}", err));
+			}
+			Search::Unloaded(false) => {
+				blocks
+					.push_head(editor, false)
+					.unwrap_or_else(|err| panic!("Couldn't load the previous block | {}", err));
+			}
+		}
+		// The syntax of the new block is needed to skip its strings and comments
+		editor.update_highlights(&mut blocks);
+	};
+
+	match found {
+		Some([position, line_num]) => {
+			// Clear the highlighted selection of text
+			editor.selection.is_empty = true;
+			navigation_keys::jump_to(editor, line_num, position);
+		}
+		None => editor.set_status_message("No matching bracket"),
+	}
+}
+
+// Split the syntax segments of a line so that each bracket at the (sorted) byte indices is its own segment
+pub fn split_brackets<'a>(
+	segments: Vec<(&'a str, Option<TokenKind>)>,
+	brackets: &[usize],
+) -> Vec<(&'a str, Option<TokenKind>)> {
+	// Byte index of the start of each segment
+	let mut position = 0;
+	let mut split = Vec::with_capacity(segments.len() + brackets.len() * 2);
+	for (text, kind) in segments {
+		let (start, end) = (position, position + text.len());
+		// The byte index of the text that hasn't been split off yet
+		let mut rest = start;
+		// Split off the text before (and the bracket at) each bracket in the segment
+		for bracket in brackets.iter().filter(|idx| start <= **idx && **idx < end) {
+			if *bracket > rest {
+				split.push((&text[rest - start..bracket - start], kind));
+			}
+			split.push((&text[bracket - start..bracket - start + 1], kind));
+			rest = bracket + 1;
+		}
+		if rest < end {
+			split.push((&text[rest - start..], kind));
+		}
+		position = end;
+	}
+	split
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+// Get the position of the bracket at (or else just before) the cursor
+fn bracket_at(blocks: &Blocks, [position, line_num]: [usize; 2]) -> Option<[usize; 2]> {
+	let (line, tokens) = line_at(blocks, line_num)?;
+	let is_bracket = |idx: usize, ch: char| {
+		BRACKETS
+			.iter()
+			.any(|(open, close)| ch == *open || ch == *close)
+			&& !is_skipped(tokens, idx)
+	};
+	// The character at the cursor
+	if let Some(ch) = line[position..].chars().next() {
+		if is_bracket(position, ch) {
+			return Some([position, line_num]);
+		}
+	}
+	// The character before the cursor
+	let (idx, ch) = line[..position].char_indices().next_back()?;
+	is_bracket(idx, ch).then_some([idx, line_num])
+}
+
+// Search the loaded blocks for the bracket that matches the bracket at the position
+fn search(blocks: &Blocks, [position, line_num]: [usize; 2]) -> Search {
+	let (line, _) = line_at(blocks, line_num).unwrap();
+	let ch = line[position..].chars().next().unwrap();
+	// The bracket to look for and the direction to look in
+	let (bracket, target, forward) = match BRACKETS.iter().find(|(open, _)| *open == ch) {
+		Some((open, close)) => (*open, *close, true),
+		None => {
+			let (open, close) = BRACKETS.iter().find(|(_, close)| *close == ch).unwrap();
+			(*close, *open, false)
+		}
+	};
+
+	// The number of brackets that are still open (including the one at the position)
+	let mut depth = 0;
+	let mut current = line_num;
+	loop {
+		let Some((line, tokens)) = line_at(blocks, current) else {
+			// Stop at the start or end of the file (or search the next unloaded block)
+			let is_loaded = match forward {
+				true => blocks.tail_block + 1 >= blocks.max_blocks,
+				false => blocks.head_block == 0,
+			};
+			return match is_loaded {
+				true => Search::Unmatched,
+				false => Search::Unloaded(forward),
+			};
+		};
+		// The brackets of the line (starting from the position on its first line)
+		let mut chars: Vec<(usize, char)> = line
+			.char_indices()
+			.filter(|(idx, _)| match (current == line_num, forward) {
+				(false, _) => true,
+				(true, true) => *idx >= position,
+				(true, false) => *idx <= position,
+			})
+			.filter(|(idx, ch)| (*ch == bracket || *ch == target) && !is_skipped(tokens, *idx))
+			.collect();
+		if !forward {
+			chars.reverse();
+		}
+
+		for (idx, ch) in chars {
+			match ch == bracket {
+				true => depth += 1,
+				false => depth -= 1,
+			}
+			if depth == 0 {
+				return Search::Found([idx, current]);
+			}
+		}
+
+		// Move onto the next (or previous) line
+		match (forward, current) {
+			(false, 0) => return Search::Unmatched,
+			(false, _) => current -= 1,
+			(true, _) => current += 1,
+		}
+	}
+}
+
+// Get the text (and the syntax tokens) of a loaded line
+fn line_at(blocks: &Blocks, line_num: usize) -> Option<(&String, &[Token])> {
+	let mut start = blocks.starting_line_num;
+	for block in &blocks.blocks_list {
+		if line_num >= start && line_num < start + block.len {
+			let idx = line_num - start;
+			let tokens = match block.highlights.get(idx) {
+				Some(Some(highlight)) => highlight.tokens.as_slice(),
+				_ => &[],
+			};
+			return Some((&block.content[idx], tokens));
+		}
+		start += block.len;
+	}
+	None
+}
+
+// Check if the text at the byte index is in a string or comment (brackets there aren't matched)
+fn is_skipped(tokens: &[Token], idx: usize) -> bool {
+	tokens.iter().any(|token| {
+		token.start <= idx
+			&& idx < token.end
+			&& matches!(token.kind, TokenKind::String | TokenKind::Comment)
+	})
+}
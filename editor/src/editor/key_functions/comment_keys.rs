@@ -0,0 +1,193 @@
+// Contains the logic for commenting and uncommenting lines (or blocks) of text
+
+use super::{highlight_keys, line_keys, EditorSpace};
+
+// Files of unknown languages use C style comments
+const DEFAULT_LINE_COMMENT: &str = "//";
+const DEFAULT_BLOCK_COMMENT: (&str, &str) = ("/*", "*/");
+
+// Comment (or uncomment if they're all commented) the selected lines (or the cursor's line)
+pub fn toggle_line_comment(editor: &mut EditorSpace) {
+	// The token that starts a comment in the language
+	let token = match editor.language {
+		Some(language) => language.line_comment(),
+		None => Some(DEFAULT_LINE_COMMENT),
+	};
+	let Some(token) = token else {
+		no_comments(editor, "line");
+		return;
+	};
+	let (first, last) = highlight_keys::selected_lines(editor);
+	let (anchor, cursor) = highlight_keys::selection_ends(editor);
+	line_keys::start_edit(editor);
+
+	// Blank lines aren't commented
+	let lines = line_keys::read_lines(editor, first, last);
+	let is_blank = |line: &String| line.trim().is_empty();
+	// The comments line up at the smallest indentation of the lines
+	let column = lines
+		.iter()
+		.filter(|line| !is_blank(line))
+		.map(|line| line.len() - line.trim_start().len())
+		.min()
+		.unwrap_or(0);
+	// Only uncomment if every line is commented
+	let uncomment = lines
+		.iter()
+		.filter(|line| !is_blank(line))
+		.all(|line| line.trim_start().starts_with(token));
+
+	// The position and number of bytes added to (or removed from) each line
+	let mut shifts = Vec::new();
+	for (idx, line) in lines.iter().enumerate() {
+		let line_num = first + idx;
+		let (text, shift) = match (is_blank(line), uncomment) {
+			(true, _) => (line.clone(), (0, 0)),
+			// Remove the token (and the space after it)
+			(false, true) => {
+				let start = line.len() - line.trim_start().len();
+				let mut end = start + token.len();
+				if line[end..].starts_with(' ') {
+					end += 1;
+				}
+				(
+					String::from(&line[..start]) + &line[end..],
+					(start, -((end - start) as isize)),
+				)
+			}
+			// Add the token at the column (with a space after it)
+			(false, false) => {
+				let comment = format!("{} ", token);
+				(
+					format!("{}{}{}", &line[..column], comment, &line[column..]),
+					(column, comment.len() as isize),
+				)
+			}
+		};
+		shifts.push(shift);
+		if text != *line {
+			line_keys::update_line(editor, text, line_num);
+		}
+	}
+
+	// Move the anchor and cursor along with their text
+	let shift = |[position, line_num]: [usize; 2]| {
+		let Some((column, shift)) = shifts.get(line_num.wrapping_sub(first)) else {
+			return [position, line_num];
+		};
+		match *shift < 0 {
+			// Text before the comment doesn't move
+			_ if position < *column => [position, line_num],
+			// Positions in a removed token move to where it was
+			true => [
+				position.saturating_sub(shift.unsigned_abs()).max(*column),
+				line_num,
+			],
+			// A position at the column stays before the inserted token
+			false if position == *column => [position, line_num],
+			false => [position + *shift as usize, line_num],
+		}
+	};
+	highlight_keys::select_range(editor, shift(anchor), shift(cursor));
+}
+
+// Wrap the selection (or the cursor's line) in a block comment (or unwrap it if it's already commented)
+pub fn toggle_block_comment(editor: &mut EditorSpace) {
+	// The tokens that start and end a block comment in the language
+	let tokens = match editor.language {
+		Some(language) => language.block_comment(),
+		None => Some(DEFAULT_BLOCK_COMMENT),
+	};
+	let Some((open, close)) = tokens else {
+		no_comments(editor, "block");
+		return;
+	};
+	let (anchor, cursor) = highlight_keys::selection_ends(editor);
+	let is_empty = editor.selection.is_empty;
+	let (selection_start, selection_end) = (editor.selection.start, editor.selection.end);
+	line_keys::start_edit(editor);
+
+	// The start and end of the text to comment (the text of the cursor's line without a selection)
+	let (start, end) = match is_empty {
+		true => {
+			let line = line_keys::read_lines(editor, cursor[1], cursor[1]).remove(0);
+			let indent = line.len() - line.trim_start().len();
+			(
+				[indent, cursor[1]],
+				[line.trim_end().len().max(indent), cursor[1]],
+			)
+		}
+		false => (selection_start, selection_end),
+	};
+	let mut first = line_keys::read_lines(editor, start[1], start[1]).remove(0);
+	let mut last = match end[1] == start[1] {
+		true => first.clone(),
+		false => line_keys::read_lines(editor, end[1], end[1]).remove(0),
+	};
+
+	// Check if the text is already wrapped in a comment
+	let is_commented = first[start[0]..].starts_with(open)
+		&& last[..end[0]].ends_with(close)
+		&& (start[1] != end[1] || end[0] - start[0] >= open.len() + close.len());
+	let new_end = match is_commented {
+		// Remove the tokens (and the spaces inside of them)
+		true => {
+			let close_start = match last[..end[0] - close.len()].ends_with(' ') {
+				true => end[0] - close.len() - 1,
+				false => end[0] - close.len(),
+			};
+			last.replace_range(close_start..end[0], "");
+			if start[1] == end[1] {
+				first = last.clone();
+			}
+			let open_end = match first[start[0] + open.len()..].starts_with(' ') {
+				true => start[0] + open.len() + 1,
+				false => start[0] + open.len(),
+			};
+			first.replace_range(start[0]..open_end, "");
+			// The end moves back by the removed tokens (on the same line)
+			match start[1] == end[1] {
+				true => [close_start - (open_end - start[0]), end[1]],
+				false => [close_start, end[1]],
+			}
+		}
+		// Add the tokens around the text
+		false => {
+			let (open, close) = (format!("{} ", open), format!(" {}", close));
+			last.insert_str(end[0], &close);
+			if start[1] == end[1] {
+				first = last.clone();
+			}
+			first.insert_str(start[0], &open);
+			match start[1] == end[1] {
+				true => [end[0] + open.len() + close.len(), end[1]],
+				false => [end[0] + close.len(), end[1]],
+			}
+		}
+	};
+	if start[1] != end[1] {
+		line_keys::update_line(editor, last, end[1]);
+	}
+	line_keys::update_line(editor, first, start[1]);
+
+	// Select the (un)commented text (keeping the direction of the selection)
+	match is_empty {
+		true => highlight_keys::select_range(editor, new_end, new_end),
+		false if anchor == selection_start => highlight_keys::select_range(editor, start, new_end),
+		false => highlight_keys::select_range(editor, new_end, start),
+	}
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+// Tell the user that the language doesn't have comments of the kind
+fn no_comments(editor: &mut EditorSpace, kind: &str) {
+	let name = editor
+		.language
+		.map_or("This language", |language| language.name());
+	editor.set_status_message(format!("{} doesn't have {} comments", name, kind));
+}
@@ -1,10 +1,24 @@
 use super::{
-	editing_keys, navigation_keys, Blocks, ClipboardProvider, EditorSpace, IndexedParallelIterator,
-	IntoParallelIterator, ParallelIterator, UnicodeSegmentation,
+	block_selection, editing_keys, multi_cursor, navigation_keys, undo_redo, Blocks, EditorSpace,
+	IntoParallelIterator, ParallelIterator, StackChoice, UnicodeSegmentation,
 };
 
 // Copy a selection of text to the clipboard
 pub fn copy_to_clipboard(editor: &mut EditorSpace) {
+	// With multiple cursors, copy the text of every cursor as a block
+	if !editor.cursors.is_empty() {
+		let text = block_selection::copy_text(editor);
+		editor.clipboard.set_block_contents(text);
+		return;
+	}
+	// Get the selected text (or the current line)
+	let text = copy_text(editor);
+	// Write to the clipboard
+	editor.clipboard.set_contents(text);
+}
+
+// Get the text that is copied (the selection, or the current line if nothing is selected)
+pub fn copy_text(editor: &mut EditorSpace) -> String {
 	// Only copy if the selection exists
 	if !editor.selection.is_empty {
 		// Start of the highlighted selection
@@ -25,35 +39,89 @@ pub fn copy_to_clipboard(editor: &mut EditorSpace) {
 			),
 		};
 
-		// Write to the clipboard
-		editor
-			.clipboard
-			.as_mut()
-			.unwrap()
-			.set_contents(lines.into_par_iter().collect::<String>())
-			.unwrap();
+		// Join the lines of the selection
+		lines.into_par_iter().collect::<String>()
 	// If selection is empty, copy the entire current line
 	} else {
-		// Get the current line
-		let line = editor.blocks.as_ref().unwrap().get_current_line();
-		// Copy the current line
-		editor
-			.clipboard
-			.as_mut()
-			.unwrap()
-			.set_contents(line)
-			.unwrap();
+		editor.blocks.as_ref().unwrap().get_current_line()
 	}
 }
 
 // Paste text from the clipboard
 pub fn paste_from_clipboard(editor: &mut EditorSpace) {
+	// The text content of the clipboard
+	let text = editor.clipboard.get_contents();
+	// Text that was copied as a block is pasted as a block
+	if editor.clipboard.is_block(&text) {
+		block_selection::paste_block(editor, &text);
+		return;
+	}
+	// With multiple cursors, paste at every cursor (which can't be cycled through the kill ring)
+	if !editor.cursors.is_empty() {
+		multi_cursor::edit(editor, true, |editor| paste_text(editor, &text));
+		return;
+	}
+	paste_text(editor, &text);
+	// The clipboard's text is the most recent entry in the kill ring
+	if !text.is_empty() {
+		editor.clipboard.set_yank(0);
+	}
+}
+
+// Paste the next older text in the kill ring (replacing the text that was just pasted)
+pub fn paste_previous(editor: &mut EditorSpace) {
+	// The number of texts in the kill ring
+	let len = editor.clipboard.kill_ring_len();
+	if len == 0 {
+		editor.set_status_message("Nothing has been copied");
+		return;
+	}
+	let index = match editor.clipboard.last_yank() {
+		// If the last input was a paste, undo it and paste the next older text
+		Some(index) => {
+			undo_redo(editor, StackChoice::Undo);
+			(index + 1) % len
+		}
+		// Otherwise, paste the most recently copied text
+		None => 0,
+	};
+
+	// Paste the text from the kill ring
+	let text = editor.clipboard.kill_ring_entry(index).unwrap();
+	paste_text(editor, &text);
+	editor.clipboard.set_yank(index);
+	// Show which entry of the kill ring was pasted
+	editor.set_status_message(format!("Pasted kill ring entry {} of {}", index + 1, len));
+}
+
+// Yank the selected text (or the current line) to a named register
+pub fn yank_to_register(editor: &mut EditorSpace, name: char) {
+	let text = copy_text(editor);
+	editor.clipboard.set_register(name, text);
+	editor.set_status_message(format!("Yanked to register {}", name));
+}
+
+// Paste the text in a named register
+pub fn paste_from_register(editor: &mut EditorSpace, name: char) {
+	match editor.clipboard.get_register(name) {
+		Some(text) => paste_text(editor, &text),
+		None => {
+			editor.set_status_message(format!("Register {} is empty", name));
+		}
+	}
+}
+
+// Paste text into the editor at the cursor (as a single undoable change)
+pub fn paste_text(editor: &mut EditorSpace, text: &str) {
+	// Add a new undo state
+	editor.update_unredo_stack(true);
+
 	// Delete selection to paste over
 	if !editor.selection.is_empty {
 		editor.delete_selection();
 	}
-	// The text content of the clipboard
-	let text = paste_subroutines::get_clipboard_content(editor);
+	// The lines of the text
+	let text = paste_subroutines::split_text(text);
 	// Split the current line of text about the cursor
 	let (mut before_cursor, after_cursor) = paste_subroutines::split_line(editor);
 
@@ -68,10 +136,14 @@ pub fn paste_from_clipboard(editor: &mut EditorSpace) {
 
 // Call the copy function and delete the selection
 pub fn cut(editor: &mut EditorSpace) {
-	// Get the current editor state
-	let state = editor.get_unredo_state();
+	// With multiple cursors, cut the text of every cursor as a block
+	if !editor.cursors.is_empty() {
+		copy_to_clipboard(editor);
+		block_selection::delete(editor);
+		return;
+	}
 	// Add a new undo state
-	editor.unredo_stack.auto_update(state, true);
+	editor.update_unredo_stack(true);
 
 	// Copy the selection to the clipboard
 	copy_to_clipboard(editor);
@@ -87,10 +159,7 @@ pub fn cut(editor: &mut EditorSpace) {
 
 // Subroutines for copying to the clipboard
 mod copy_subroutines {
-	use super::{
-		Blocks, EditorSpace, IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
-		UnicodeSegmentation,
-	};
+	use super::{Blocks, EditorSpace, IntoParallelIterator, ParallelIterator, UnicodeSegmentation};
 	use std::io::Error;
 
 	pub fn copy_lines(
@@ -107,12 +176,12 @@ mod copy_subroutines {
 			if line_num % editor.height == 0 {
 				blocks.check_blocks(editor);
 			}
-			// Get the indices of the graphemes
+			// Get the graphemes (and their byte indices)
 			let indices = &blocks
 				.get_some_line(line_num)?
-				.graphemes(true)
-				.map(String::from)
-				.collect::<Vec<String>>();
+				.grapheme_indices(true)
+				.map(|(idx, graph)| (idx, String::from(graph)))
+				.collect::<Vec<(usize, String)>>();
 
 			// Get the line of the selection
 			let line = match copy_line(start, end, line_num, blocks, indices) {
@@ -143,7 +212,7 @@ mod copy_subroutines {
 		end: (usize, usize),
 		line_num: usize,
 		blocks: &mut Blocks,
-		indices: &Vec<String>,
+		indices: &Vec<(usize, String)>,
 	) -> Result<String, Error> {
 		// If only one line
 		if start.1 == end.1 {
@@ -161,13 +230,12 @@ mod copy_subroutines {
 	}
 
 	// Collect the graphemes of a one line selection into a string
-	fn one_line_selection(indices: &Vec<String>, start: usize, end: usize) -> String {
+	fn one_line_selection(indices: &Vec<(usize, String)>, start: usize, end: usize) -> String {
 		indices
 			.into_par_iter()
-			.enumerate()
 			.filter_map(|(idx, graph)| {
 				// Get all graphemes on the line between the two indices
-				if idx >= start && idx < end {
+				if *idx >= start && *idx < end {
 					Some(String::from(graph))
 				} else {
 					None
@@ -177,13 +245,12 @@ mod copy_subroutines {
 	}
 
 	// Collect the graphemes of the first line of a multiline selection into a string
-	fn first_line_selection(indices: &Vec<String>, start: usize) -> String {
+	fn first_line_selection(indices: &Vec<(usize, String)>, start: usize) -> String {
 		indices
 			.into_par_iter()
-			.enumerate()
 			.filter_map(|(idx, graph)| {
 				// Get all graphemes on the line after the index
-				if idx >= start {
+				if *idx >= start {
 					Some(String::from(graph))
 				} else {
 					None
@@ -193,13 +260,12 @@ mod copy_subroutines {
 	}
 
 	// Collect the graphemes of the last line of a multiline seelction into a string
-	fn last_line_selection(indices: &Vec<String>, end: usize) -> String {
+	fn last_line_selection(indices: &Vec<(usize, String)>, end: usize) -> String {
 		indices
 			.into_par_iter()
-			.enumerate()
 			.filter_map(|(idx, graph)| {
 				// Get all graphemes on the line before the index
-				if idx < end {
+				if *idx < end {
 					Some(String::from(graph))
 				} else {
 					None
@@ -216,11 +282,8 @@ mod copy_subroutines {
 */
 
 // Subroutines for pasting from clipboard
-mod paste_subroutines {
-	use super::{
-		super::super::key_functions, editing_keys, navigation_keys, ClipboardProvider, EditorSpace,
-		UnicodeSegmentation,
-	};
+pub mod paste_subroutines {
+	use super::{super::super::key_functions, editing_keys, navigation_keys, EditorSpace};
 
 	// Get the text on the line before and after the cursor
 	pub fn split_line(editor: &mut EditorSpace) -> (String, String) {
@@ -234,13 +297,13 @@ mod paste_subroutines {
 		(before_cursor, after_cursor)
 	}
 
-	// Get the text content of the clipboard (and the length of the text)
-	pub fn get_clipboard_content(editor: &mut EditorSpace) -> Vec<String> {
-		// Get the text stored in the clipboard
-		let text = editor.clipboard.as_mut().unwrap().get_contents().unwrap();
-
-		// The text from the clipboard as a text vector
-		text.split('\n').map(String::from).collect::<Vec<String>>()
+	// Split the pasted text into lines (terminals may paste with CRLF or CR line endings)
+	pub fn split_text(text: &str) -> Vec<String> {
+		text.replace("\r\n", "\n")
+			.replace('\r', "\n")
+			.split('\n')
+			.map(String::from)
+			.collect::<Vec<String>>()
 	}
 
 	// Paste a single line from the clipboard
@@ -255,17 +318,9 @@ mod paste_subroutines {
 		// Create the full line of text (after paste)
 		let text = before_cursor.to_owned() + &after_cursor;
 
-		// Store the original editor width
-		let original_width = editor.width;
-		// Make the editor width HUGE
-		editor.width = usize::MAX;
-
 		// Update the current line with this new text
 		editor.blocks.as_mut().unwrap().update_current_line(text);
 
-		// Reset editor width
-		editor.width = original_width;
-
 		// Move cursor to new location
 		realign_cursor(editor, before_cursor.to_string());
 	}
@@ -282,11 +337,6 @@ mod paste_subroutines {
 		// Prepend the last line of the clipboard to the text after the cursor
 		let after_cursor = text.last().unwrap().to_owned() + &after_cursor;
 
-		// Store the original editor width
-		let original_width = editor.width;
-		// Make the editor width HUGE
-		editor.width = usize::MAX;
-
 		// Update the current line of text with the part before the cursor
 		editor
 			.blocks
@@ -299,15 +349,12 @@ mod paste_subroutines {
 
 		// Paste the multiline clipboard content
 		paste_loop(editor, text, after_cursor);
-
-		// Reset editor width
-		editor.width = original_width;
 	}
 
 	// Move to the new location after updating the first line of the mutliline paste
 	fn realign_cursor(editor: &mut EditorSpace, before_cursor: String) {
-		// Length of the text before the cursor
-		let before_len = before_cursor.graphemes(true).count();
+		// Length (in bytes) of the text before the cursor
+		let before_len = before_cursor.len();
 
 		// Move to the beginning of the line
 		navigation_keys::home_key(editor, true);
@@ -330,7 +377,7 @@ mod paste_subroutines {
 			// All lines in the middle
 			} else {
 				// Add a new line
-				editing_keys::enter_key(editor);
+				editing_keys::new_line(editor);
 				// Update this new line
 				editor.blocks.as_mut().unwrap().update_current_line(line);
 				// Move to the end of this new line
@@ -341,11 +388,11 @@ mod paste_subroutines {
 
 	// Paste the last line of the clipboard content
 	fn last_line_paste(editor: &mut EditorSpace, text: &[String], after_cursor: &str) {
-		// Length of the text after the cursor
-		let after_len = text.last().unwrap().graphemes(true).count();
+		// Length (in bytes) of the pasted text on the last line
+		let after_len = text.last().unwrap().len();
 
 		// Add a newline
-		editing_keys::enter_key(editor);
+		editing_keys::new_line(editor);
 		// Update the text of the newline
 		editor
 			.blocks
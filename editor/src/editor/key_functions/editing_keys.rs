@@ -1,22 +1,29 @@
 use super::{
+	auto_pair, indent,
 	navigation_keys::{down_arrow, end_key, home_key, left_arrow, up_arrow},
 	EditorSpace,
 };
 
 // Functionality of pressing a normal character key
 pub fn char_key(editor: &mut EditorSpace, code: char) {
+	// Typing an opening bracket (or quote) with a selection surrounds it
+	if auto_pair::surround_selection(editor, code) {
+		return;
 	// If there is a highlighted selection
-	if !editor.selection.is_empty {
+	} else if !editor.selection.is_empty {
 		// Add an undo state and delete the selection
 		delete_subroutines::selection_delete(editor);
+	// Typing a closing bracket (or quote) in front of the same one moves over it
+	} else if auto_pair::skip_closer(editor, code) {
+		return;
 	// Update progress toward a new undo state if the current code is a space
 	} else {
-		// Get the current editor state
-		let state = editor.get_unredo_state();
 		// Add a new unredo state if necessary
-		editor.unredo_stack.auto_update(state, false);
+		editor.update_unredo_stack(false);
 	}
 
+	// Closing brackets at the start of a line de-indent it
+	indent::deindent_closer(editor, code);
 	// Insert the character into the correct line in the correct block
 	editor
 		.blocks
@@ -28,6 +35,8 @@ pub fn char_key(editor: &mut EditorSpace, code: char) {
 	editor.text_position += 1;
 	editor.cursor_position[0] += 1;
 	editor.stored_position = editor.cursor_position[0];
+	// Opening brackets (and quotes) are closed after the cursor
+	auto_pair::insert_closer(editor, code);
 }
 
 // Functionality for the tab key
@@ -37,31 +46,18 @@ pub fn tab_key(editor: &mut EditorSpace) {
 		// Add an undo state and delete the selection
 		delete_subroutines::selection_delete(editor);
 	} else {
-		// Get the current editor state
-		let state = editor.get_unredo_state();
 		// Add a new unredo state if necessary
-		editor.unredo_stack.auto_update(state, false);
+		editor.update_unredo_stack(false);
 	}
 
-	// Insert tab character into the line
-	editor
-		.blocks
-		.as_mut()
-		.unwrap()
-		.insert_char_in_line(editor.text_position, '\t');
-
-	// Move cursor
-	editor.text_position += 1;
-	editor.cursor_position[0] += editor.config.tab_width;
-	editor.stored_position = editor.cursor_position[0];
+	// Insert a tab character (or spaces up to the next level of indentation)
+	indent::insert_indent(editor);
 }
 
 // Functionality of pressing the enter key
 pub fn enter_key(editor: &mut EditorSpace) {
-	// Get the current editor state
-	let state = editor.get_unredo_state();
 	// Add a new undo state
-	editor.unredo_stack.auto_update(state, true);
+	editor.update_unredo_stack(true);
 
 	// If there is a highlighted selection
 	if !editor.selection.is_empty {
@@ -69,6 +65,12 @@ pub fn enter_key(editor: &mut EditorSpace) {
 		editor.delete_selection();
 	}
 
+	// Insert the new line (indented like the current line)
+	indent::indented_new_line(editor);
+}
+
+// Insert a new line at the cursor and move onto it (without adding an undo state)
+pub fn new_line(editor: &mut EditorSpace) {
 	// Insert a new line and truncate the current one (after the cursor)
 	editor
 		.blocks
@@ -96,9 +98,20 @@ pub fn backspace(editor: &mut EditorSpace) {
 			// Backspace at beginning of the line
 			delete_subroutines::backspace_beginning_of_line(editor);
 		// Otherwise, just move cursor left
-		} else if editor.text_position != 0 {
+		// Backspace over a level of indentation with spaces
+		} else if editor.text_position != 0 && !indent::backspace_indent(editor) {
+			// An empty pair of brackets (or quotes) is deleted together
+			let is_pair = auto_pair::in_empty_pair(editor);
 			// Backspace normally, deleting one char
 			delete_subroutines::backspace_normally(editor);
+			// Delete the closer after the cursor
+			if is_pair {
+				editor
+					.blocks
+					.as_mut()
+					.unwrap()
+					.delete_char_in_line(editor.text_position);
+			}
 		}
 	} else {
 		// Add a new undo state and delete the selection
@@ -132,10 +145,8 @@ mod delete_subroutines {
 	// Backspace at the beginning of line, moving to the above line
 	pub fn backspace_beginning_of_line(editor: &mut EditorSpace) {
 		if editor.file_length > 0 {
-			// Get the current editor state
-			let state = editor.get_unredo_state();
 			// Add a new undo state
-			editor.unredo_stack.auto_update(state, true);
+			editor.update_unredo_stack(true);
 
 			// Move up one line
 			up_arrow(editor);
@@ -158,10 +169,8 @@ mod delete_subroutines {
 
 	// Backspace after the beginning of the line deletes a char normally
 	pub fn backspace_normally(editor: &mut EditorSpace) {
-		// Get the current editor state
-		let state = editor.get_unredo_state();
 		// Add a new unredo state if necessary
-		editor.unredo_stack.auto_update(state, false);
+		editor.update_unredo_stack(false);
 
 		// Move left
 		left_arrow(editor, true);
@@ -176,10 +185,8 @@ mod delete_subroutines {
 
 	// Check if there is a selection that needs to be deleted
 	pub fn selection_delete(editor: &mut EditorSpace) {
-		// Get the current editor state
-		let state = editor.get_unredo_state();
 		// Add a new undo state
-		editor.unredo_stack.auto_update(state, true);
+		editor.update_unredo_stack(true);
 		// Delete the selection
 		editor.delete_selection();
 	}
@@ -204,10 +211,8 @@ mod delete_subroutines {
 
 	// Delete a single character normally
 	fn delete_normally(editor: &mut EditorSpace) {
-		// Get the current editor state
-		let state = editor.get_unredo_state();
 		// Add a new unredo state if necessary
-		editor.unredo_stack.auto_update(state, false);
+		editor.update_unredo_stack(false);
 
 		// Delete next char
 		editor
@@ -219,10 +224,8 @@ mod delete_subroutines {
 
 	// Delete at the end of a line
 	fn delete_end(editor: &mut EditorSpace, line_num: usize) {
-		// Get the current editor state
-		let state = editor.get_unredo_state();
 		// Add a new undo state
-		editor.unredo_stack.auto_update(state, true);
+		editor.update_unredo_stack(true);
 
 		// Delete the below line and append its text content to the current line
 		editor
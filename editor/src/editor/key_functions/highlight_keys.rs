@@ -1,5 +1,5 @@
 use super::{
-	navigation_keys::{down_arrow, end_key, home_key, left_arrow, right_arrow, up_arrow},
+	navigation_keys::{down_arrow, end_key, home_key, jump_to, left_arrow, right_arrow, up_arrow},
 	EditorSpace,
 };
 
@@ -120,3 +120,79 @@ pub fn highlight_page_down(editor: &mut EditorSpace) {
 		}
 	}
 }
+
+// Anchor a new selection at the cursor (the point that highlighting with the mouse extends from)
+pub fn anchor_selection(editor: &mut EditorSpace) {
+	// Clear any existing selection
+	editor.selection.is_empty = true;
+	// The original position of the selection is the anchor
+	highlight_subroutines::init_subroutines::init_original_positions(editor);
+}
+
+// Highlight the text between the anchor of the selection and the cursor
+pub fn highlight_to_cursor(editor: &mut EditorSpace) {
+	// The anchor of the selection
+	let anchor = [
+		editor.selection.original_text_position.0,
+		editor.selection.original_text_position.1,
+	];
+	// The location of the cursor in the text
+	let cursor = [
+		editor.text_position,
+		editor.get_line_num(editor.cursor_position[1]),
+	];
+	// Order the points by line number (and then by position on the line)
+	if (anchor[1], anchor[0]) <= (cursor[1], cursor[0]) {
+		editor.selection.start = anchor;
+		editor.selection.end = cursor;
+	} else {
+		editor.selection.start = cursor;
+		editor.selection.end = anchor;
+	}
+	// The selection is empty if the cursor is on the anchor
+	editor.selection.is_empty = anchor == cursor;
+}
+
+// Select from the anchor to the cursor (as [text position, line number])
+pub fn select_range(editor: &mut EditorSpace, anchor: [usize; 2], cursor: [usize; 2]) {
+	// Move to the anchor of the selection
+	jump_to(editor, anchor[1], anchor[0]);
+	anchor_selection(editor);
+	// Keep the blocks of the selection loaded while moving to the cursor
+	editor.selection.is_empty = false;
+	// Highlight up to the cursor
+	jump_to(editor, cursor[1], cursor[0]);
+	highlight_to_cursor(editor);
+}
+
+// Get the first and last selected lines (a selection ending at the start of a line doesn't include it)
+pub fn selected_lines(editor: &EditorSpace) -> (usize, usize) {
+	let (start, end) = (editor.selection.start, editor.selection.end);
+	match editor.selection.is_empty {
+		// Only the cursor's line without a selection
+		true => {
+			let line_num = editor.get_line_num(editor.cursor_position[1]);
+			(line_num, line_num)
+		}
+		false if end[0] == 0 && end[1] > start[1] => (start[1], end[1] - 1),
+		false => (start[1], end[1]),
+	}
+}
+
+// Get the anchor of the selection and the cursor (as [text position, line number])
+pub fn selection_ends(editor: &EditorSpace) -> ([usize; 2], [usize; 2]) {
+	let cursor = [
+		editor.text_position,
+		editor.get_line_num(editor.cursor_position[1]),
+	];
+	match editor.selection.is_empty {
+		true => (cursor, cursor),
+		false => (
+			[
+				editor.selection.original_text_position.0,
+				editor.selection.original_text_position.1,
+			],
+			cursor,
+		),
+	}
+}
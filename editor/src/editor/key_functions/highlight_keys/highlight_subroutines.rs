@@ -206,7 +206,7 @@ pub fn end_subroutine(editor: &mut EditorSpace) {
 			Subroutines for the init_selection function
 ===================================================================
 */
-mod init_subroutines {
+pub mod init_subroutines {
 	use super::{EditorSpace, Movement};
 
 	/* Initialize the three original position fields (original_cursor_position,
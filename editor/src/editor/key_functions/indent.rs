@@ -0,0 +1,366 @@
+// Contains the logic for indenting with tabs or spaces and automatically indenting new lines
+
+use std::collections::HashMap;
+
+use super::{
+	editing_keys, highlight_keys, line_keys,
+	navigation_keys::{self, end_key, left_arrow, up_arrow},
+	AutoIndent, Config, EditorSpace,
+};
+
+// The opening and closing brackets
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+// The widths of indentation with spaces that can be detected
+const DETECTED_WIDTHS: std::ops::RangeInclusive<usize> = 2..=8;
+
+// The whitespace that a file is indented with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Indentation {
+	// Tab characters
+	Tabs,
+	// The given number of spaces for each level of indentation
+	Spaces(usize),
+}
+
+impl Indentation {
+	// Get the indentation set by the config
+	pub fn from_config(config: &Config) -> Self {
+		match config.soft_tabs {
+			true => Indentation::Spaces(config.tab_width),
+			false => Indentation::Tabs,
+		}
+	}
+
+	// Get the whitespace of one level of indentation
+	pub fn unit(self) -> String {
+		match self {
+			Indentation::Tabs => String::from("\t"),
+			Indentation::Spaces(width) => " ".repeat(width),
+		}
+	}
+}
+
+// Detect whether the file is indented with tabs or spaces (and how many) from its first block
+pub fn detect_indentation(editor: &EditorSpace) -> Indentation {
+	let default = Indentation::from_config(&editor.config);
+	let Some(block) = editor.blocks.as_ref().unwrap().blocks_list.first() else {
+		return default;
+	};
+
+	// Count the lines indented with tabs and with spaces
+	let (mut tab_lines, mut space_lines) = (0, 0);
+	// Count how often the indentation changes by each number of spaces
+	let mut widths: HashMap<usize, usize> = HashMap::new();
+	let mut previous = 0;
+	for line in block.content.iter().filter(|line| !line.trim().is_empty()) {
+		let spaces = line.chars().take_while(|c| *c == ' ').count();
+		match line.chars().next() {
+			Some('\t') => tab_lines += 1,
+			Some(' ') => space_lines += 1,
+			_ => (),
+		}
+		// Only lines that are indented with spaces change the indentation by spaces
+		if !line[spaces..].starts_with('\t') {
+			let change = spaces.abs_diff(previous);
+			if DETECTED_WIDTHS.contains(&change) {
+				*widths.entry(change).or_default() += 1;
+			}
+			previous = spaces;
+		}
+	}
+
+	// The most common change in indentation is the width (preferring the smaller width)
+	let width = widths
+		.into_iter()
+		.max_by_key(|(width, count)| (*count, std::cmp::Reverse(*width)))
+		.map(|(width, _)| width);
+	match (tab_lines.cmp(&space_lines), width) {
+		(std::cmp::Ordering::Greater, _) => Indentation::Tabs,
+		(std::cmp::Ordering::Less, Some(width)) => Indentation::Spaces(width),
+		_ => default,
+	}
+}
+
+// Insert one level of indentation at the cursor (spaces line up with the next level)
+pub fn insert_indent(editor: &mut EditorSpace) {
+	let whitespace = match editor.indentation {
+		Indentation::Tabs => String::from("\t"),
+		Indentation::Spaces(width) => " ".repeat(width - editor.cursor_position[0] % width),
+	};
+	insert_whitespace(editor, &whitespace);
+}
+
+// Backspace over the spaces of one level of indentation (returns false if not in the indentation)
+pub fn backspace_indent(editor: &mut EditorSpace) -> bool {
+	let Indentation::Spaces(width) = editor.indentation else {
+		return false;
+	};
+	// Only the spaces at the start of a line are indentation
+	let line = editor.blocks.as_ref().unwrap().get_current_line();
+	let before = &line[..editor.text_position.min(line.len())];
+	if before.is_empty() || !before.chars().all(|c| c == ' ') {
+		return false;
+	}
+
+	// Add a new undo state if necessary
+	editor.update_unredo_stack(false);
+	// Remove the spaces back to the previous level of indentation
+	for _i in 0..(before.len() - 1) % width + 1 {
+		left_arrow(editor, true);
+		editor
+			.blocks
+			.as_mut()
+			.unwrap()
+			.delete_char_in_line(editor.text_position);
+	}
+	true
+}
+
+// Check if the selection is on multiple lines (which Tab indents instead of replacing)
+pub fn is_multiline_selection(editor: &EditorSpace) -> bool {
+	!editor.selection.is_empty
+		&& editor.selection.start[1] != editor.selection.end[1]
+		&& editor.cursors.is_empty()
+}
+
+// Indent (or outdent) the selected lines (or the cursor's line) by one level, keeping the selection
+pub fn indent_lines(editor: &mut EditorSpace, outdent: bool) {
+	// Remove the extra cursors and add a new undo state for all of the lines
+	line_keys::start_edit(editor);
+	// The anchor of the selection and the cursor (as [text position, line number])
+	let (anchor, cursor) = highlight_keys::selection_ends(editor);
+	// The selected lines
+	let (first, last) = highlight_keys::selected_lines(editor);
+
+	// The number of bytes added to (or removed from) the start of each line
+	let unit = editor.indentation.unit();
+	let mut shifts = Vec::new();
+	editor.selection.is_empty = true;
+	for row in first..=last {
+		navigation_keys::jump_to(editor, row, 0);
+		let line = editor.blocks.as_ref().unwrap().get_current_line();
+		let text = match outdent {
+			// Remove a tab (or up to one level of spaces)
+			true => {
+				let removed = match line.starts_with('\t') {
+					true => 1,
+					false => line
+						.chars()
+						.take_while(|c| *c == ' ')
+						.count()
+						.min(unit.len()),
+				};
+				shifts.push(-(removed as isize));
+				String::from(&line[removed..])
+			}
+			// Empty lines aren't indented
+			false if line.is_empty() => {
+				shifts.push(0);
+				line
+			}
+			false => {
+				shifts.push(unit.len() as isize);
+				unit.clone() + &line
+			}
+		};
+		editor
+			.blocks
+			.as_mut()
+			.unwrap()
+			.update_some_line(text, row)
+			.unwrap_or_else(|err| panic!("Couldn't indent line {} | {}", row + 1, err));
+	}
+
+	// Move the anchor and cursor along with their text
+	let shift = |[position, line_num]: [usize; 2]| match shifts.get(line_num.wrapping_sub(first)) {
+		Some(shift) if *shift < 0 => [position.saturating_sub(shift.unsigned_abs()), line_num],
+		// Positions at the start of a line stay at the start (before the new indentation)
+		Some(shift) if position > 0 => [position + *shift as usize, line_num],
+		_ => [position, line_num],
+	};
+	highlight_keys::select_range(editor, shift(anchor), shift(cursor));
+}
+
+// Convert the indentation of the selected lines (or the entire file) between tabs and spaces
+pub fn convert_indentation(editor: &mut EditorSpace) {
+	// Remove the extra cursors and add a new undo state for the entire conversion
+	line_keys::start_edit(editor);
+	// Switch between tabs and spaces
+	let (converted, width) = match editor.indentation {
+		Indentation::Tabs => (
+			Indentation::Spaces(editor.config.tab_width),
+			editor.config.tab_width,
+		),
+		Indentation::Spaces(width) => (Indentation::Tabs, width),
+	};
+	// The lines to convert
+	let (first, last) = match editor.selection.is_empty {
+		true => (0, editor.file_length - 1),
+		false => highlight_keys::selected_lines(editor),
+	};
+
+	// The cursor's line and distance from the end of its line (which converting doesn't change)
+	let line_num = editor.get_line_num(editor.cursor_position[1]);
+	let from_end = editor.blocks.as_ref().unwrap().get_current_line().len() - editor.text_position;
+
+	// Replace the indentation of each line
+	editor.selection.is_empty = true;
+	for row in first..=last {
+		navigation_keys::jump_to(editor, row, 0);
+		let line = editor.blocks.as_ref().unwrap().get_current_line();
+		let indent = leading_whitespace(&line);
+		// The width of the indentation (in spaces, with each tab moving to the next tab stop)
+		let columns = indent.chars().fold(0, |columns, c| match c {
+			'\t' => columns + width - columns % width,
+			_ => columns + 1,
+		});
+		let converted_indent = match converted {
+			Indentation::Tabs => "\t".repeat(columns / width) + &" ".repeat(columns % width),
+			Indentation::Spaces(_) => " ".repeat(columns),
+		};
+		if converted_indent != indent {
+			let text = converted_indent + &line[indent.len()..];
+			editor
+				.blocks
+				.as_mut()
+				.unwrap()
+				.update_some_line(text, row)
+				.unwrap_or_else(|err| panic!("Couldn't convert line {} | {}", row + 1, err));
+		}
+	}
+
+	// Move back to the cursor's position
+	navigation_keys::jump_to(editor, line_num, 0);
+	let line_len = editor.blocks.as_ref().unwrap().get_current_line().len();
+	navigation_keys::jump_to(editor, line_num, line_len.saturating_sub(from_end));
+	editor.indentation = converted;
+	editor.set_status_message(match converted {
+		Indentation::Tabs => "Converted indentation to tabs",
+		Indentation::Spaces(_) => "Converted indentation to spaces",
+	});
+}
+
+// Insert a new line at the cursor that is indented by the auto indent of the file's language
+pub fn indented_new_line(editor: &mut EditorSpace) {
+	let auto_indent = auto_indent(editor);
+	let line = editor.blocks.as_ref().unwrap().get_current_line();
+	let (before, after) = line.split_at(editor.text_position.min(line.len()));
+	// The leading whitespace of the line (up to the cursor)
+	let base = leading_whitespace(before);
+
+	// The indentation of the new line
+	let mut indent = match auto_indent {
+		AutoIndent::Off => String::new(),
+		_ => base.clone(),
+	};
+	// The indentation of the closing bracket after the cursor (if it's moved onto its own line)
+	let mut closer_indent = None;
+	let opener = before.trim_end().chars().last();
+	if auto_indent == AutoIndent::Smart && opener.is_some_and(|opener| opens(editor, opener)) {
+		indent.push_str(&editor.indentation.unit());
+		// Split a pair of brackets (e.g. `{|}`) so the closing bracket is below the new line
+		if after.trim_start().chars().next() == opener.and_then(closing_bracket) {
+			closer_indent = Some(base);
+		}
+	}
+
+	// Insert the indented line
+	editing_keys::new_line(editor);
+	insert_whitespace(editor, &indent);
+	// Move the closing bracket onto the next line (back at the original indentation)
+	if let Some(closer_indent) = closer_indent {
+		editing_keys::new_line(editor);
+		insert_whitespace(editor, &closer_indent);
+		up_arrow(editor);
+		end_key(editor, true);
+	}
+}
+
+// De-indent the line by one level if a closing bracket is typed at the start of the line
+pub fn deindent_closer(editor: &mut EditorSpace, code: char) {
+	// Only closing brackets of the language's opening brackets de-indent
+	let Some((opener, _)) = BRACKETS.iter().find(|(_, closer)| *closer == code) else {
+		return;
+	};
+	if auto_indent(editor) != AutoIndent::Smart || !opens(editor, *opener) {
+		return;
+	}
+	// Only de-indent when there is nothing but whitespace before the cursor
+	let line = editor.blocks.as_ref().unwrap().get_current_line();
+	let before = &line[..editor.text_position.min(line.len())];
+	if !before.chars().all(|c| c == ' ' || c == '\t') {
+		return;
+	}
+
+	// Remove a tab (or up to one level of spaces)
+	let level = match (before.ends_with('\t'), editor.indentation) {
+		(true, _) => 1,
+		(false, Indentation::Spaces(width)) => before.len().min(width),
+		(false, Indentation::Tabs) => before.len().min(editor.config.tab_width),
+	};
+	for _i in 0..level {
+		left_arrow(editor, true);
+		editor
+			.blocks
+			.as_mut()
+			.unwrap()
+			.delete_char_in_line(editor.text_position);
+	}
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+// Get the auto indent of the file's language (or the default auto indent)
+fn auto_indent(editor: &EditorSpace) -> AutoIndent {
+	editor
+		.language
+		.and_then(|language| editor.config.language_indent.get(language.name()))
+		.copied()
+		.unwrap_or(editor.config.auto_indent)
+}
+
+// Check if a character at the end of a line indents the next line
+fn opens(editor: &EditorSpace, code: char) -> bool {
+	match editor.language {
+		Some(language) => language.indent_openers().contains(&code),
+		// Brackets indent in files of unknown languages
+		None => BRACKETS.iter().any(|(opener, _)| *opener == code),
+	}
+}
+
+// Get the closing bracket of an opening bracket
+fn closing_bracket(opener: char) -> Option<char> {
+	BRACKETS
+		.iter()
+		.find(|(open, _)| *open == opener)
+		.map(|(_, closer)| *closer)
+}
+
+// Get the whitespace at the start of a line
+fn leading_whitespace(line: &str) -> String {
+	line.chars()
+		.take_while(|c| *c == ' ' || *c == '\t')
+		.collect()
+}
+
+// Insert whitespace at the cursor (without adding an undo state)
+fn insert_whitespace(editor: &mut EditorSpace, whitespace: &str) {
+	for code in whitespace.chars() {
+		editor
+			.blocks
+			.as_mut()
+			.unwrap()
+			.insert_char_in_line(editor.text_position, code);
+		// Tabs are a tab's width wide
+		editor.text_position += 1;
+		editor.cursor_position[0] += match code {
+			'\t' => editor.config.tab_width,
+			_ => 1,
+		};
+	}
+	editor.stored_position = editor.cursor_position[0];
+}
@@ -0,0 +1,160 @@
+// Contains the logic for moving, duplicating, joining, and deleting whole lines
+
+use super::{editing_keys, highlight_keys, multi_cursor, navigation_keys, EditorSpace};
+
+// Move the selected lines (or the cursor's line) up one line
+pub fn move_lines_up(editor: &mut EditorSpace) {
+	move_lines(editor, true);
+}
+
+// Move the selected lines (or the cursor's line) down one line
+pub fn move_lines_down(editor: &mut EditorSpace) {
+	move_lines(editor, false);
+}
+
+// Insert a copy of the selected lines (or the cursor's line) below them and select the copy
+pub fn duplicate_lines(editor: &mut EditorSpace) {
+	let (first, last) = highlight_keys::selected_lines(editor);
+	let (anchor, cursor) = highlight_keys::selection_ends(editor);
+	start_edit(editor);
+
+	// Insert each copied line below the last line
+	let lines = read_lines(editor, first, last);
+	let count = lines.len();
+	navigation_keys::jump_to(editor, last, usize::MAX);
+	for (idx, line) in lines.into_iter().enumerate() {
+		editing_keys::new_line(editor);
+		update_line(editor, line, last + 1 + idx);
+		navigation_keys::end_key(editor, true);
+	}
+
+	// Select the copy
+	let shift = |[position, line_num]: [usize; 2]| [position, line_num + count];
+	highlight_keys::select_range(editor, shift(anchor), shift(cursor));
+}
+
+// Join the next line onto the cursor's line (or join the selected lines), collapsing the whitespace between them
+pub fn join_lines(editor: &mut EditorSpace) {
+	let (first, last) = highlight_keys::selected_lines(editor);
+	// Without a selection of multiple lines, join the next line
+	let last = last.max(first + 1);
+	if last >= editor.file_length {
+		editor.set_status_message("No line to join");
+		return;
+	}
+	start_edit(editor);
+
+	for _i in first..last {
+		// The line and the next line
+		let lines = read_lines(editor, first, first + 1);
+		let (line, next) = (&lines[0], &lines[1]);
+		// Delete the whitespace at the end of the line, the newline, and the indentation of the next line
+		let start = line.trim_end().len();
+		let end = next.len() - next.trim_start().len();
+		highlight_keys::select_range(editor, [start, first], [end, first + 1]);
+		editor.delete_selection();
+		// Separate the text of the lines by a single space
+		if !line.trim_end().is_empty() && !next.trim_start().is_empty() {
+			editor
+				.blocks
+				.as_mut()
+				.unwrap()
+				.insert_char_in_line(editor.text_position, ' ');
+			editor.text_position += 1;
+			editor.cursor_position[0] += 1;
+			editor.stored_position = editor.cursor_position[0];
+		}
+	}
+}
+
+// Delete the selected lines (or the cursor's line), adding them to the kill ring
+pub fn delete_lines(editor: &mut EditorSpace) {
+	let (first, last) = highlight_keys::selected_lines(editor);
+	start_edit(editor);
+	// The text of the deleted lines
+	let lines = read_lines(editor, first, last);
+
+	// Select from the start of the first line to the start of the line after the last line
+	let (anchor, cursor) = if last + 1 < editor.file_length {
+		([0, first], [0, last + 1])
+	// The last line of the file has no line after it, so delete the newline before the first line
+	} else if first > 0 {
+		let previous = read_lines(editor, first - 1, first - 1);
+		([previous[0].len(), first - 1], [usize::MAX, last])
+	// Deleting every line leaves a single empty line
+	} else {
+		([0, 0], [usize::MAX, last])
+	};
+	highlight_keys::select_range(editor, anchor, cursor);
+	editor.delete_selection();
+	navigation_keys::home_key(editor, true);
+
+	// The deleted lines can be pasted back
+	editor.clipboard.set_contents(lines.join("\n") + "\n");
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+// Move the selected lines up (or down) past the line above (or below) them
+fn move_lines(editor: &mut EditorSpace, up: bool) {
+	let (first, last) = highlight_keys::selected_lines(editor);
+	// Lines can't be moved past the start or end of the file
+	if (up && first == 0) || (!up && last + 1 >= editor.file_length) {
+		return;
+	}
+	let (anchor, cursor) = highlight_keys::selection_ends(editor);
+	start_edit(editor);
+
+	// The moved lines and the line that they're moved past
+	let (top, bottom) = match up {
+		true => (first - 1, last),
+		false => (first, last + 1),
+	};
+	let mut lines = read_lines(editor, top, bottom);
+	// Swap the line that's moved past to the other side of the moved lines
+	match up {
+		true => lines.rotate_left(1),
+		false => lines.rotate_right(1),
+	}
+	for (idx, line) in lines.into_iter().enumerate() {
+		navigation_keys::jump_to(editor, top + idx, 0);
+		update_line(editor, line, top + idx);
+	}
+
+	// Move the selection (and the cursor) with the lines
+	let shift = |[position, line_num]: [usize; 2]| match up {
+		true => [position, line_num - 1],
+		false => [position, line_num + 1],
+	};
+	highlight_keys::select_range(editor, shift(anchor), shift(cursor));
+}
+
+// Remove the extra cursors and add a new undo state for the entire operation
+pub fn start_edit(editor: &mut EditorSpace) {
+	multi_cursor::clear(editor);
+	editor.update_unredo_stack(true);
+}
+
+// Get the text of the lines from first to last (loading them in if needed)
+pub fn read_lines(editor: &mut EditorSpace, first: usize, last: usize) -> Vec<String> {
+	(first..=last)
+		.map(|line_num| {
+			navigation_keys::jump_to(editor, line_num, 0);
+			editor.blocks.as_ref().unwrap().get_current_line()
+		})
+		.collect()
+}
+
+// Replace the text of a line
+pub fn update_line(editor: &mut EditorSpace, text: String, line_num: usize) {
+	editor
+		.blocks
+		.as_mut()
+		.unwrap()
+		.update_some_line(text, line_num)
+		.unwrap_or_else(|err| panic!("Couldn't update line {} | {}", line_num + 1, err));
+}
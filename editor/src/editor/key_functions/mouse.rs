@@ -0,0 +1,191 @@
+// Contains the logic for clicking, dragging, and scrolling with the mouse
+
+use std::time::{Duration, Instant};
+
+use super::{
+	check_cursor_end_line, highlight_keys, navigation_keys, EditorSpace, UnicodeSegmentation,
+};
+
+// Clicks at the same position within this time of each other are double (or triple) clicks
+const MULTI_CLICK_TIME: Duration = Duration::from_millis(500);
+// The number of lines scrolled by each notch of the mouse wheel
+const SCROLL_LINES: usize = 3;
+
+// A click of the left mouse button
+#[derive(Clone, Copy, Debug)]
+pub struct Click {
+	// When the click happened
+	time: Instant,
+	// The (column, row) of the terminal that was clicked
+	position: (u16, u16),
+	// The number of clicks in a row (1 for single, 2 for double, and 3 for triple clicks)
+	count: usize,
+}
+
+// Move the cursor to the clicked text (double clicks select a word and triple clicks a line)
+pub fn click(editor: &mut EditorSpace, column: u16, row: u16) {
+	// Ignore clicks outside of the text area of the editor widget
+	let Some((x, y)) = text_area_position(editor, column as usize, row as usize) else {
+		editor.click = None;
+		return;
+	};
+	// Count the clicks in a row at this position (cycling back to a single click)
+	let count = match editor.click {
+		Some(click)
+			if click.position == (column, row) && click.time.elapsed() < MULTI_CLICK_TIME =>
+		{
+			click.count % 3 + 1
+		}
+		_ => 1,
+	};
+	editor.click = Some(Click {
+		time: Instant::now(),
+		position: (column, row),
+		count,
+	});
+
+	// Move to the clicked text
+	move_to(editor, x, y);
+	match count {
+		// Select the word that was clicked
+		2 => select_word(editor),
+		// Select the line that was clicked
+		3 => select_line(editor),
+		// Dragging highlights from the clicked text
+		_ => highlight_keys::anchor_selection(editor),
+	}
+}
+
+// Highlight from the clicked text to the text under the mouse
+pub fn drag(editor: &mut EditorSpace, column: u16, row: u16) {
+	// Only drag if the click was in the editor
+	if editor.click.is_none() {
+		return;
+	}
+	// The first row and column of the text area
+	let top = editor.widget_vert_bounds.0 + 1;
+	let left = editor.widget_horz_bounds.0 + 1;
+	let (column, row) = (column as usize, row as usize);
+
+	// Scroll while dragging above or below the widget
+	if row < top {
+		navigation_keys::scroll_up(editor);
+	} else if row > top + editor.height {
+		navigation_keys::scroll_down(editor);
+	}
+	// Move to the closest text within the widget
+	move_to(
+		editor,
+		column.clamp(left, left + editor.width.saturating_sub(1)) - left,
+		row.clamp(top, top + editor.height) - top,
+	);
+	highlight_keys::highlight_to_cursor(editor);
+}
+
+// Scroll the text up with the mouse wheel
+pub fn scroll_up(editor: &mut EditorSpace) {
+	for _i in 0..SCROLL_LINES {
+		navigation_keys::scroll_up(editor);
+	}
+}
+
+// Scroll the text down with the mouse wheel
+pub fn scroll_down(editor: &mut EditorSpace) {
+	for _i in 0..SCROLL_LINES {
+		navigation_keys::scroll_down(editor);
+	}
+}
+
+// Get the (column, row) within the text area of the editor widget (None if outside of it)
+fn text_area_position(editor: &EditorSpace, column: usize, row: usize) -> Option<(usize, usize)> {
+	// The first row and column of the text area (inside of the borders)
+	let top = editor.widget_vert_bounds.0 + 1;
+	let left = editor.widget_horz_bounds.0 + 1;
+	// Check that the position is within the text area
+	match (left..left + editor.width).contains(&column)
+		&& (top..=top + editor.height).contains(&row)
+	{
+		true => Some((column - left, row - top)),
+		false => None,
+	}
+}
+
+// Move the cursor onto the text shown at the given (column, row) of the text area
+fn move_to(editor: &mut EditorSpace, x: usize, y: usize) {
+	// Nothing to move to in an empty editor
+	if editor.is_empty() {
+		return;
+	}
+	// The row of the line in the widget, the display column on the line, and the end of the visual row
+	let (row, column, row_end) = match editor.config.soft_wrap {
+		true => wrapped_target(editor, x, y),
+		false => (y, x + editor.horizontal_offset, None),
+	};
+	// Don't move past the last line of the file
+	let last_row = (editor.file_length - 1).saturating_sub(editor.get_line_num(0));
+	navigation_keys::move_to_screen_row(editor, row.min(last_row));
+	navigation_keys::move_to_column(editor, column, row_end);
+}
+
+// Get the row of the line, the display column, and the end of the visual row shown at (x, y)
+fn wrapped_target(editor: &EditorSpace, x: usize, y: usize) -> (usize, usize, Option<usize>) {
+	// The number of visual rows above the current line
+	let mut rows = 0;
+	let visible = editor.get_visible_wrap_points();
+	for (idx, points) in visible.iter().enumerate() {
+		// If the row is on this line
+		if y < rows + points.len() {
+			let row = y - rows;
+			return (
+				idx,
+				points[row].1 + x,
+				points.get(row + 1).map(|point| point.0),
+			);
+		}
+		rows += points.len();
+	}
+	// Below the last line, move to the end of the last line
+	(visible.len().saturating_sub(1), usize::MAX, None)
+}
+
+// Select the unicode word (or run of whitespace or punctuation) at the cursor
+pub fn select_word(editor: &mut EditorSpace) {
+	let line = editor.blocks.as_ref().unwrap().get_current_line();
+	// The boundaries of the word under the cursor (or before the cursor at the end of the line)
+	let (start, end) = line
+		.split_word_bound_indices()
+		.map(|(idx, word)| (idx, idx + word.len()))
+		.find(|(start, end)| *start <= editor.text_position && editor.text_position < *end)
+		.or_else(|| {
+			line.split_word_bound_indices()
+				.next_back()
+				.map(|(idx, word)| (idx, idx + word.len()))
+		})
+		.unwrap_or((0, 0));
+
+	// Move to the start of the word and anchor the selection there
+	navigation_keys::home_key(editor, false);
+	while editor.text_position < start && check_cursor_end_line(editor) {
+		navigation_keys::right_arrow(editor, false);
+	}
+	highlight_keys::anchor_selection(editor);
+	// Move to the end of the word
+	while editor.text_position < end && check_cursor_end_line(editor) {
+		navigation_keys::right_arrow(editor, true);
+	}
+	highlight_keys::highlight_to_cursor(editor);
+}
+
+// Select the line at the cursor (including its newline)
+pub fn select_line(editor: &mut EditorSpace) {
+	// Anchor the selection at the start of the line
+	navigation_keys::home_key(editor, true);
+	highlight_keys::anchor_selection(editor);
+	// Move to the end of the line
+	navigation_keys::end_key(editor, true);
+	// Move onto the start of the next line (if there is one)
+	if editor.get_line_num(editor.cursor_position[1]) + 1 < editor.file_length {
+		navigation_keys::right_arrow(editor, true);
+	}
+	highlight_keys::highlight_to_cursor(editor);
+}
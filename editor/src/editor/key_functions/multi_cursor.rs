@@ -0,0 +1,413 @@
+// Contains the logic for editing the text with multiple cursors
+
+use std::cmp::Reverse;
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Modifier};
+
+use super::{
+	display_width, highlight_keys, highlight_keys::selection::Selection, mouse, navigation_keys,
+	soft_wrap, EditorSpace, UnRedoStack, UnicodeSegmentation,
+};
+
+// The most cursors (including the primary cursor) that can be added
+pub const MAX_CURSORS: usize = 1000;
+
+// An extra cursor in the text (the editor's own cursor is the primary cursor)
+#[derive(Clone, Debug)]
+pub struct Cursor {
+	// The line number of the cursor
+	pub line_num: usize,
+	// The byte index of the cursor on its line
+	pub text_position: usize,
+	// The text highlighted by this cursor
+	pub selection: Selection,
+}
+
+impl Cursor {
+	// Create a cursor (without a selection) at the given position
+	pub fn new(line_num: usize, text_position: usize) -> Self {
+		Cursor {
+			line_num,
+			text_position,
+			selection: Selection::new(),
+		}
+	}
+
+	// Create a cursor at the end of a selection of text on a single line
+	pub fn selecting(line_num: usize, start: usize, end: usize) -> Self {
+		Cursor {
+			line_num,
+			text_position: end,
+			selection: Selection {
+				start: [start, line_num],
+				end: [end, line_num],
+				is_empty: false,
+				..Selection::new()
+			},
+		}
+	}
+
+	// Create a cursor from the editor's own cursor
+	pub fn primary(editor: &EditorSpace) -> Self {
+		Cursor {
+			line_num: editor.get_line_num(editor.cursor_position[1]),
+			text_position: editor.text_position,
+			selection: editor.selection.clone(),
+		}
+	}
+
+	// The position of the cursor (or the start of its selection) in the text
+	pub fn start(&self) -> (usize, usize) {
+		match self.selection.is_empty {
+			true => (self.line_num, self.text_position),
+			false => (self.selection.start[1], self.selection.start[0]),
+		}
+	}
+}
+
+// Apply an edit at every cursor (as a single undo state)
+pub fn edit(editor: &mut EditorSpace, force_undo: bool, mut edit: impl FnMut(&mut EditorSpace)) {
+	edit_each(editor, force_undo, |editor, _| edit(editor));
+}
+
+/* Apply an edit at every cursor (which is given the index of the cursor from the top of the file).
+The edits are made from the bottom of the file up, so the text after each cursor doesn't change. */
+pub fn edit_each(
+	editor: &mut EditorSpace,
+	force_undo: bool,
+	mut edit: impl FnMut(&mut EditorSpace, usize),
+) {
+	// Editing ends a block selection
+	editor.block_selection = None;
+	// With a single cursor, edit normally
+	if editor.cursors.is_empty() {
+		edit(editor, 0);
+		return;
+	}
+
+	// Deleting a selection always adds a new undo state
+	let force_undo = force_undo
+		|| !editor.selection.is_empty
+		|| editor
+			.cursors
+			.iter()
+			.any(|cursor| !cursor.selection.is_empty);
+	// Add the undo state for the entire edit
+	editor.update_unredo_stack(force_undo);
+	// Don't add undo states for the edits at each cursor
+	let unredo_stack = std::mem::replace(&mut editor.unredo_stack, UnRedoStack::paused());
+	// The row of the widget that the primary cursor is on
+	let row = editor.cursor_position[1];
+
+	// All of the cursors (the primary cursor is first)
+	let mut cursors = vec![Cursor::primary(editor)];
+	cursors.append(&mut editor.cursors);
+	// Order the cursors from the bottom of the file up
+	let mut order: Vec<usize> = (0..cursors.len()).collect();
+	order.sort_by_key(|idx| Reverse(cursors[*idx].start()));
+
+	/* The position of each cursor after its edit, counted from the end of the file and the end
+	of its line (which the edits at the cursors above don't change) */
+	let mut from_end = vec![(0, 0); cursors.len()];
+	for (rank, idx) in order.into_iter().enumerate() {
+		activate(editor, &cursors[idx]);
+		edit(editor, cursors.len() - 1 - rank);
+		let line = editor.blocks.as_ref().unwrap().get_current_line();
+		from_end[idx] = (
+			editor.file_length - editor.get_line_num(editor.cursor_position[1]),
+			line.len() - editor.text_position,
+		);
+	}
+	editor.unredo_stack = unredo_stack;
+
+	// Find where each cursor ended up in the text (visiting its line only loads the blocks it's in)
+	editor.selection.is_empty = true;
+	let positions: Vec<(usize, usize)> = from_end
+		.into_iter()
+		.map(|(lines, bytes)| {
+			let line_num = editor.file_length - lines;
+			navigation_keys::jump_to(editor, line_num, 0);
+			let line = editor.blocks.as_ref().unwrap().get_current_line();
+			(line_num, line.len() - bytes)
+		})
+		.collect();
+	// Move back to the primary cursor
+	navigation_keys::jump_to(editor, positions[0].0, positions[0].1);
+	restore_row(editor, row);
+	// Keep the other cursors (merging any that ended up in the same place)
+	for (idx, (line_num, text_position)) in positions.iter().enumerate().skip(1) {
+		if !positions[..idx].contains(&(*line_num, *text_position)) {
+			editor.cursors.push(Cursor::new(*line_num, *text_position));
+		}
+	}
+}
+
+// Remove the extra cursors (and end any block selection)
+pub fn clear(editor: &mut EditorSpace) {
+	editor.cursors.clear();
+	editor.block_selection = None;
+}
+
+// Add a cursor on the line above the top cursor
+pub fn add_cursor_above(editor: &mut EditorSpace) {
+	// The line of the top cursor
+	let top = editor
+		.cursors
+		.iter()
+		.map(|cursor| cursor.line_num)
+		.fold(editor.get_line_num(editor.cursor_position[1]), usize::min);
+	if top > 0 {
+		add_cursor_on_line(editor, top - 1);
+	}
+}
+
+// Add a cursor on the line below the bottom cursor
+pub fn add_cursor_below(editor: &mut EditorSpace) {
+	// The line of the bottom cursor
+	let bottom = editor
+		.cursors
+		.iter()
+		.map(|cursor| cursor.line_num)
+		.fold(editor.get_line_num(editor.cursor_position[1]), usize::max);
+	if bottom + 1 < editor.file_length {
+		add_cursor_on_line(editor, bottom + 1);
+	}
+}
+
+/* Add a cursor at the next occurrence of the selected text (after the last added cursor).
+Without a selection, the word at the cursor is selected first. */
+pub fn add_next_occurrence(editor: &mut EditorSpace) {
+	// The selected text
+	let Some(text) = selected_text(editor) else {
+		return;
+	};
+	// The start of the last added cursor's selection (or the primary selection)
+	let last = editor.cursors.last().map_or(
+		(editor.selection.start[1], editor.selection.start[0]),
+		|cursor| cursor.start(),
+	);
+
+	// The occurrences that don't have a cursor yet
+	let matches: Vec<(usize, usize)> = find_matches(editor, &text)
+		.into_iter()
+		.filter(|start| !has_cursor(editor, *start))
+		.collect();
+	// The next occurrence after the last cursor (wrapping around to the start of the file)
+	match matches
+		.iter()
+		.find(|start| **start > last)
+		.or(matches.first())
+	{
+		Some((line_num, start)) => {
+			let cursor = Cursor::selecting(*line_num, *start, start + text.len());
+			editor.cursors.push(cursor);
+			editor.set_status_message(format!("{} cursors", editor.cursors.len() + 1));
+		}
+		None => editor.set_status_message("No more occurrences"),
+	}
+}
+
+// Add a cursor at every occurrence of the selected text
+pub fn add_all_occurrences(editor: &mut EditorSpace) {
+	// Select the word at the cursor if nothing is selected
+	if editor.selection.is_empty {
+		clear(editor);
+		mouse::select_word(editor);
+	}
+	// The selected text
+	let Some(text) = selected_text(editor) else {
+		return;
+	};
+
+	// The start of the primary selection
+	let primary = (editor.selection.start[1], editor.selection.start[0]);
+	let matches: Vec<(usize, usize)> = find_matches(editor, &text)
+		.into_iter()
+		.filter(|start| *start != primary)
+		.collect();
+	// Only the first occurrences get a cursor (up to the limit)
+	editor.cursors = matches
+		.iter()
+		.take(MAX_CURSORS - 1)
+		.map(|(line_num, start)| Cursor::selecting(*line_num, *start, start + text.len()))
+		.collect();
+	match matches.len() < MAX_CURSORS {
+		true => editor.set_status_message(format!("{} cursors", editor.cursors.len() + 1)),
+		false => editor.set_status_message(format!(
+			"Limited to {} cursors (of {} occurrences)",
+			MAX_CURSORS,
+			matches.len() + 1
+		)),
+	}
+}
+
+// Draw the extra cursors (and their selections) over the text area
+pub fn draw_cursors(editor: &EditorSpace, buffer: &mut Buffer, area: Rect) {
+	for cursor in editor.cursors.iter() {
+		// Highlight the cursor's selection
+		if !cursor.selection.is_empty && cursor.selection.start[1] == cursor.selection.end[1] {
+			let line = get_loaded_line(editor, cursor.line_num);
+			let selected = line.grapheme_indices(true).filter(|(loc, _)| {
+				(cursor.selection.start[0]..cursor.selection.end[0]).contains(loc)
+			});
+			for (loc, grapheme) in selected {
+				let Some((x, y)) = get_cell(editor, area, cursor.line_num, loc) else {
+					continue;
+				};
+				// Highlight every column of the grapheme (that's within the text area)
+				let width = display_width(grapheme, editor.config.tab_width) as u16;
+				for x in x..(x + width).min(area.x + area.width) {
+					buffer
+						.get_mut(x, y)
+						.set_bg(editor.config.theme.selection_highlight);
+				}
+			}
+		}
+		// Draw the cursor as a reversed cell
+		if let Some((x, y)) = get_cell(editor, area, cursor.line_num, cursor.text_position) {
+			buffer.get_mut(x, y).modifier.insert(Modifier::REVERSED);
+		}
+	}
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+// Move the editor's cursor onto a cursor (and highlight its selection)
+pub fn activate(editor: &mut EditorSpace, cursor: &Cursor) {
+	// Clear the current selection (so the blocks before the cursor can be unloaded)
+	editor.selection.is_empty = true;
+	navigation_keys::jump_to(editor, cursor.line_num, cursor.text_position);
+	// Highlight from the start to the end of the selection
+	if !cursor.selection.is_empty {
+		let [start, end] = [cursor.selection.start, cursor.selection.end];
+		highlight_keys::select_range(editor, start, end);
+	}
+}
+
+// Add a cursor on the given line (at the column of the primary cursor)
+fn add_cursor_on_line(editor: &mut EditorSpace, line_num: usize) {
+	// The primary cursor (and its column and row in the widget)
+	let primary = Cursor::primary(editor);
+	let (column, row) = (editor.stored_position, editor.cursor_position[1]);
+
+	// Visit the line to find the text at the column
+	editor.selection.is_empty = true;
+	navigation_keys::jump_to(editor, line_num, 0);
+	navigation_keys::move_to_column(editor, column, None);
+	let cursor = Cursor::new(line_num, editor.text_position);
+
+	// Move back to the primary cursor
+	activate(editor, &primary);
+	editor.stored_position = column;
+	restore_row(editor, row);
+	editor.cursors.push(cursor);
+	editor.set_status_message(format!("{} cursors", editor.cursors.len() + 1));
+}
+
+// Scroll so that the cursor is on the given row of the widget again (if possible)
+fn restore_row(editor: &mut EditorSpace, row: usize) {
+	let row = row.min(editor.height);
+	// Scroll up (within the loaded blocks)
+	while editor.cursor_position[1] < row && editor.scroll_offset > 0 {
+		editor.scroll_offset -= 1;
+		editor.cursor_position[1] += 1;
+	}
+	// Scroll down
+	while editor.cursor_position[1] > row {
+		editor.scroll_offset += 1;
+		editor.cursor_position[1] -= 1;
+	}
+}
+
+/* Get the text of the primary selection (if it's on a single line).
+Without a selection, the word at the cursor is selected first. */
+fn selected_text(editor: &mut EditorSpace) -> Option<String> {
+	if editor.selection.is_empty {
+		mouse::select_word(editor);
+		return None;
+	}
+	if editor.selection.start[1] != editor.selection.end[1] {
+		editor.set_status_message("Only text on a single line can be matched");
+		return None;
+	}
+	let line = get_loaded_line(editor, editor.selection.start[1]);
+	Some(String::from(
+		&line[editor.selection.start[0]..editor.selection.end[0]],
+	))
+}
+
+// Find the (line number, byte index) of every occurrence of the text in the file
+fn find_matches(editor: &mut EditorSpace, text: &str) -> Vec<(usize, usize)> {
+	// Load in all the blocks in the file
+	let mut blocks = editor.blocks.as_ref().unwrap().clone();
+	blocks.load_all_blocks(editor);
+
+	// The occurrences on each line (numbered from the first loaded line)
+	blocks
+		.blocks_list
+		.iter()
+		.flat_map(|block| block.content.iter())
+		.enumerate()
+		.flat_map(|(idx, line)| {
+			let line_num = blocks.starting_line_num + idx;
+			line.match_indices(text)
+				.map(move |(start, _)| (line_num, start))
+				.collect::<Vec<(usize, usize)>>()
+		})
+		.collect()
+}
+
+// Check if there is already a cursor whose selection starts at the position
+fn has_cursor(editor: &EditorSpace, (line_num, start): (usize, usize)) -> bool {
+	editor.selection.start == [start, line_num]
+		|| editor
+			.cursors
+			.iter()
+			.any(|cursor| cursor.selection.start == [start, line_num])
+}
+
+// Get the given line of text if it's loaded
+fn get_loaded_line(editor: &EditorSpace, line_num: usize) -> String {
+	editor
+		.blocks
+		.as_ref()
+		.unwrap()
+		.get_some_line(line_num)
+		.unwrap_or_default()
+}
+
+// Get the cell of the text area that shows the text at the given position (if it's visible)
+fn get_cell(
+	editor: &EditorSpace,
+	area: Rect,
+	line_num: usize,
+	text_position: usize,
+) -> Option<(u16, u16)> {
+	// The line must be below the top of the widget
+	let line_idx = line_num.checked_sub(editor.get_line_num(0))?;
+	let line = get_loaded_line(editor, line_num);
+	// The display column of the position on its line
+	let column = display_width(line.get(..text_position)?, editor.config.tab_width);
+
+	// The row of the widget and the column within it
+	let (x, y) = match editor.config.soft_wrap {
+		true => {
+			let visible = editor.get_visible_wrap_points();
+			let points = visible.get(line_idx)?;
+			let row = soft_wrap::row_of(points, text_position);
+			// The visual rows of the lines above (and the rows of the line before the position)
+			let rows: usize = visible.iter().take(line_idx).map(Vec::len).sum();
+			(column - points[row].1, rows + row)
+		}
+		false => (column.checked_sub(editor.horizontal_offset)?, line_idx),
+	};
+	// Only cells within the text area are drawn
+	match x < area.width as usize && y < area.height as usize {
+		true => Some((area.x + x as u16, area.y + y as u16)),
+		false => None,
+	}
+}
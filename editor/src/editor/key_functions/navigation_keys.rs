@@ -1,6 +1,6 @@
 use super::{
-	check_cursor_begin_line, check_cursor_end_line, highlight_keys, EditorSpace, GraphemeCursor,
-	UnicodeSegmentation, UnicodeWidthStr,
+	check_cursor_begin_line, check_cursor_end_line, highlight_keys, soft_wrap, EditorSpace,
+	GraphemeCursor, UnicodeSegmentation, UnicodeWidthStr,
 };
 
 // Subroutines for the left arrow functions
@@ -11,6 +11,8 @@ mod right_subroutines;
 mod up_subroutines;
 // Subroutines for the down arrow functions
 pub mod down_subroutines;
+// Subroutines for moving by visual row when soft wrapping
+mod wrap_subroutines;
 
 /*
 =============================================
@@ -50,6 +52,12 @@ pub fn right_arrow(editor: &mut EditorSpace, will_store_cursor: bool) {
 
 // Up arrow key functionality
 pub fn up_arrow(editor: &mut EditorSpace) {
+	// If soft wrapping, move up a visual row within the line if possible
+	if editor.config.soft_wrap && wrap_subroutines::up_within_line(editor) {
+		return;
+	}
+	// The column within the visual row to keep when soft wrapping
+	let column = wrap_subroutines::visual_column(editor);
 	// The current line number
 	let line_num = editor.get_line_num(editor.cursor_position[1]);
 
@@ -70,10 +78,20 @@ pub fn up_arrow(editor: &mut EditorSpace) {
 	up_subroutines::update_block_location(editor);
 	// Realign the cursor with the stored cursor position
 	realign_cursor(editor);
+	// If soft wrapping, move onto the last visual row of the new line
+	if editor.config.soft_wrap && editor.get_line_num(editor.cursor_position[1]) != line_num {
+		wrap_subroutines::to_last_row(editor, column);
+	}
 }
 
 // Down arrow key functionality
 pub fn down_arrow(editor: &mut EditorSpace) {
+	// If soft wrapping, move down a visual row within the line if possible
+	if editor.config.soft_wrap && wrap_subroutines::down_within_line(editor) {
+		return;
+	}
+	// The column within the visual row to keep when soft wrapping
+	let column = wrap_subroutines::visual_column(editor);
 	// Line number of current line in the text
 	let line_num = editor.get_line_num(editor.cursor_position[1]);
 	// Last line that the cursor can move to
@@ -98,6 +116,10 @@ pub fn down_arrow(editor: &mut EditorSpace) {
 	}
 	// Realign the cursor with the stored cursor position
 	realign_cursor(editor);
+	// If soft wrapping, move onto the first visual row of the new line
+	if editor.config.soft_wrap && line_num < file_length {
+		wrap_subroutines::to_first_row(editor, column);
+	}
 }
 
 // Home key functionality
@@ -133,8 +155,7 @@ pub fn jump_left(editor: &mut EditorSpace, will_highlight: bool) {
 	// Get the index of the previous word
 	let index = line
 		.unicode_word_indices()
-		.filter(|(idx, _)| *idx < editor.text_position)
-		.last()
+		.rfind(|(idx, _)| *idx < editor.text_position)
 		.unwrap_or((0, ""))
 		.0;
 
@@ -218,6 +239,143 @@ pub fn page_down(editor: &mut EditorSpace) {
 	}
 }
 
+/*
+=================================================
+			Mouse movement functions
+=================================================
+*/
+
+// Scroll the text up one line (the cursor only moves if it would leave the widget)
+pub fn scroll_up(editor: &mut EditorSpace) {
+	// Scroll within the loaded blocks
+	if editor.scroll_offset > 0 {
+		editor.scroll_offset -= 1;
+	// If scrolling before the start of the block, insert a new head
+	} else if editor.blocks.as_ref().unwrap().starting_line_num > 0 {
+		up_subroutines::up_load_blocks(editor);
+	// Can't scroll before the start of the file
+	} else {
+		return;
+	}
+
+	// Keep the cursor on its line if it's still within the widget
+	if editor.cursor_position[1] < editor.height {
+		editor.cursor_position[1] += 1;
+	// Otherwise, the cursor moves up onto the bottom line of the widget
+	} else {
+		up_subroutines::update_block_location(editor);
+		realign_cursor(editor);
+	}
+	// Keep soft wrapped lines from pushing the cursor below the widget
+	while editor.config.soft_wrap
+		&& editor.cursor_position[1] > 0
+		&& editor.get_screen_cursor().1 > editor.height
+	{
+		editor.cursor_position[1] -= 1;
+		up_subroutines::update_block_location(editor);
+		realign_cursor(editor);
+	}
+}
+
+// Scroll the text down one line (the cursor only moves if it would leave the widget)
+pub fn scroll_down(editor: &mut EditorSpace) {
+	// Can't scroll past the last line of the file
+	if editor.get_line_num(0) + 1 >= editor.file_length {
+		return;
+	}
+	// Scroll down
+	editor.scroll_offset += 1;
+	// If the bottom of the widget is after the end of the block, insert a new tail
+	let blocks = editor.blocks.as_ref().unwrap();
+	if editor.get_line_num(editor.height) >= blocks.starting_line_num + blocks.len()
+		&& editor.get_line_num(editor.height) < editor.file_length
+	{
+		down_subroutines::down_load_blocks(editor);
+	}
+
+	// Keep the cursor on its line if it's still within the widget
+	if editor.cursor_position[1] > 0 {
+		editor.cursor_position[1] -= 1;
+	// Otherwise, the cursor moves down onto the top line of the widget
+	} else {
+		down_subroutines::update_block_location(editor);
+		realign_cursor(editor);
+	}
+}
+
+// Move the cursor onto the line shown at the given row of the widget (without scrolling)
+pub fn move_to_screen_row(editor: &mut EditorSpace, row: usize) {
+	// Move down to the row
+	while editor.cursor_position[1] < row {
+		down_subroutines::down_no_scroll(editor);
+		down_subroutines::update_block_location(editor);
+	}
+	// Move up to the row
+	while editor.cursor_position[1] > row {
+		editor.cursor_position[1] -= 1;
+		up_subroutines::update_block_location(editor);
+	}
+}
+
+/* Move the cursor onto the grapheme displayed at the given column of the current line.
+When soft wrapping, the cursor stays before the start of the next visual row (row_end). */
+pub fn move_to_column(editor: &mut EditorSpace, column: usize, row_end: Option<usize>) {
+	// Move right until the column is reached
+	home_key(editor, false);
+	while editor.cursor_position[0] < column
+		&& check_cursor_end_line(editor)
+		&& row_end.filter(|end| editor.text_position >= *end).is_none()
+	{
+		right_arrow(editor, false);
+	}
+	// Move back onto the start of a tab or wide grapheme (or back onto the visual row)
+	if editor.cursor_position[0] > column || row_end.is_some_and(|end| editor.text_position >= end)
+	{
+		left_arrow(editor, false);
+	}
+	// Keep this column when moving up and down
+	editor.stored_position = editor.cursor_position[0];
+}
+
+// Move the cursor to the given position (line number and byte index) in the text
+pub fn jump_to(editor: &mut EditorSpace, line_num: usize, text_position: usize) {
+	// Don't move past the last line of the file
+	let line_num = line_num.min(editor.file_length.saturating_sub(1));
+	// The line shown at the top of the widget
+	let top = editor.get_line_num(0);
+	// Load in the blocks up to the line
+	load_line(editor, line_num);
+
+	let blocks = editor.blocks.as_ref().unwrap();
+	// The index of the line (and the top line) within the loaded blocks
+	let row = line_num - blocks.starting_line_num;
+	let top_row = top.saturating_sub(blocks.starting_line_num);
+	// The block (and the line within it) that the line is in
+	let (block_num, block_line) = blocks.get_location(line_num).unwrap();
+	editor.blocks.as_mut().unwrap().curr_position = [block_num, block_line];
+
+	// Scroll as little as possible to show the line in the widget
+	editor.scroll_offset = match row {
+		// Lines above the widget are shown at the top
+		row if row < top_row => row,
+		// Lines below the widget are shown at the bottom
+		row if row > top_row + editor.height => row.saturating_sub(editor.height),
+		_ => top_row,
+	};
+	editor.cursor_position[1] = row - editor.scroll_offset;
+	editor.clamp_cursor();
+
+	// Move right until the position is reached
+	home_key(editor, true);
+	while editor.text_position < text_position && check_cursor_end_line(editor) {
+		right_arrow(editor, true);
+	}
+	// Keep the cursor's visual row within the widget when soft wrapping
+	if editor.config.soft_wrap {
+		editor.scroll_to_wrapped_cursor();
+	}
+}
+
 /*
 ==============================
 			Helper
@@ -236,3 +394,23 @@ fn realign_cursor(editor: &mut EditorSpace) {
 		right_arrow(editor, false);
 	}
 }
+
+// Load in the previous (or next) blocks until the line is in the loaded blocks
+fn load_line(editor: &mut EditorSpace, line_num: usize) {
+	let mut blocks = editor.blocks.take().unwrap();
+	while line_num < blocks.starting_line_num {
+		blocks
+			.push_head(editor, true)
+			.unwrap_or_else(|err| panic!("Couldn't load the previous block | {}", err));
+	}
+	while line_num >= blocks.starting_line_num + blocks.len() {
+		/* Move the scroll offset and tracked location to the end of the loaded blocks first
+		(so that unloading the head block doesn't move them before the start of the blocks) */
+		editor.scroll_offset = blocks.len().saturating_sub(1);
+		blocks.curr_position = [blocks.blocks_list.len() - 1, 0];
+		blocks
+			.push_tail(editor, true)
+			.unwrap_or_else(|err| panic!("Couldn't load the next block | {}", err));
+	}
+	editor.blocks = Some(blocks);
+}
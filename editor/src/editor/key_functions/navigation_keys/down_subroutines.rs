@@ -6,7 +6,7 @@ pub fn down_no_scroll(editor: &mut EditorSpace) {
 }
 
 // Logic for loading blocks when moving down
-fn down_load_blocks(editor: &mut EditorSpace) {
+pub fn down_load_blocks(editor: &mut EditorSpace) {
 	// Clone the blocks
 	let mut blocks = editor.blocks.clone();
 	// Insert a new block at the tail (and remove head if necessary)
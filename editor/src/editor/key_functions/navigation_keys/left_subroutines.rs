@@ -59,7 +59,7 @@ fn left_not_tab(editor: &mut EditorSpace, line_num: usize, line: &str, will_stor
 	let character = line
 		.grapheme_indices(true)
 		.filter_map(|(loc, graph)| if loc == pos { Some(graph) } else { None })
-		.last()
+		.next_back()
 		.unwrap_or_default();
 
 	// Get the width of the current grapheme
@@ -57,7 +57,7 @@ fn right_not_tab(editor: &mut EditorSpace, line_num: usize, line: &str, will_sto
 	let character = line
 		.grapheme_indices(true)
 		.filter_map(|(loc, graph)| if loc == pos { Some(graph) } else { None })
-		.last()
+		.next_back()
 		.unwrap_or_default();
 
 	// Update editor text position
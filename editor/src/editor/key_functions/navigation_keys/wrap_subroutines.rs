@@ -0,0 +1,84 @@
+// Subroutines for moving up and down by visual row when soft wrapping
+
+use super::{check_cursor_end_line, home_key, left_arrow, right_arrow, soft_wrap, EditorSpace};
+
+// Get the wrap points of the line the cursor is on
+fn current_wrap_points(editor: &EditorSpace) -> Vec<(usize, usize)> {
+	// The line of text
+	let line = editor.blocks.as_ref().unwrap().get_current_line();
+	editor.get_wrap_points(&line)
+}
+
+// Get the stored column of the cursor relative to the start of its visual row
+pub fn visual_column(editor: &EditorSpace) -> usize {
+	let points = current_wrap_points(editor);
+	// The visual row the cursor is on
+	let row = soft_wrap::row_of(&points, editor.text_position);
+	editor.stored_position.saturating_sub(points[row].1)
+}
+
+// Move up one visual row within the current line (returns false if on the first row)
+pub fn up_within_line(editor: &mut EditorSpace) -> bool {
+	let points = current_wrap_points(editor);
+	// The visual row the cursor is on
+	let row = soft_wrap::row_of(&points, editor.text_position);
+	// If on the first row, the cursor needs to move to the above line
+	if row == 0 {
+		return false;
+	}
+	let column = visual_column(editor);
+	move_to_row(editor, &points, row - 1, column);
+	true
+}
+
+// Move down one visual row within the current line (returns false if on the last row)
+pub fn down_within_line(editor: &mut EditorSpace) -> bool {
+	let points = current_wrap_points(editor);
+	// The visual row the cursor is on
+	let row = soft_wrap::row_of(&points, editor.text_position);
+	// If on the last row, the cursor needs to move to the below line
+	if row + 1 >= points.len() {
+		return false;
+	}
+	let column = visual_column(editor);
+	move_to_row(editor, &points, row + 1, column);
+	true
+}
+
+// Move to the given column on the first visual row of the current line
+pub fn to_first_row(editor: &mut EditorSpace, column: usize) {
+	let points = current_wrap_points(editor);
+	move_to_row(editor, &points, 0, column);
+}
+
+// Move to the given column on the last visual row of the current line
+pub fn to_last_row(editor: &mut EditorSpace, column: usize) {
+	let points = current_wrap_points(editor);
+	move_to_row(editor, &points, points.len() - 1, column);
+}
+
+// Move as close as possible to the given column on the given visual row of the line
+fn move_to_row(editor: &mut EditorSpace, points: &[(usize, usize)], row: usize, column: usize) {
+	// The start of the row
+	let (start, start_column) = points[row];
+	// The start of the next row (if there is one)
+	let next_start = points.get(row + 1).map(|point| point.0);
+
+	// Move to the start of the row
+	home_key(editor, false);
+	while editor.text_position < start && check_cursor_end_line(editor) {
+		right_arrow(editor, false);
+	}
+	// Move right until the column is reached
+	while editor.cursor_position[0] < start_column + column && check_cursor_end_line(editor) {
+		right_arrow(editor, false);
+		// Don't move onto the next row
+		if Some(editor.text_position) == next_start {
+			left_arrow(editor, false);
+			break;
+		}
+	}
+
+	// Keep the column when moving through shorter rows
+	editor.stored_position = start_column + column;
+}
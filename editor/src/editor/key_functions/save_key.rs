@@ -1,4 +1,4 @@
-use super::{Blocks, EditorSpace, File, OpenOptions, ParallelExtend};
+use super::{Blocks, Compression, EditorSpace, File, FileBacking, OpenOptions, ParallelExtend};
 use std::io::{Error, Write};
 
 // Save key combo functionality
@@ -17,13 +17,23 @@ pub fn save_key_combo(editor: &mut EditorSpace, in_debug_mode: bool, debug_filen
 	match in_debug_mode {
 		// If in debug mode, write to debug_filename
 		true => _ = save_file(debug_filename, contents),
+		// If the text was read from stdin, write to the in-memory buffer
+		false if editor.is_piped() => editor.file = save_buffer(contents),
+		// If the file is compressed, recompress it with the same format
+		false if editor.compression.is_some() => {
+			editor.file = save_compressed(&editor.filename, contents, editor.compression.unwrap())
+		}
 		// If not in debug mode, write to the regular file
-		false => editor.file = save_file(&editor.filename, contents),
+		false => editor.file = FileBacking::Disk(save_file(&editor.filename, contents)),
 	}
 
 	// Update the editor's scroll offset and Blocks
 	post_save_editor_update(editor)
-		.unwrap_or_else(|err| panic!("{}::save_key_combo: line {} | {}", file!(), line!(), err))
+		.unwrap_or_else(|err| panic!("{}::save_key_combo: line {} | {}", file!(), line!(), err));
+
+	// Tell the user that the file was saved
+	let message = format!("Saved {} lines", editor.file_length);
+	editor.set_status_message(message);
 }
 
 /* Subroutines */
@@ -57,6 +67,38 @@ fn save_file(filename: &str, contents: Vec<String>) -> File {
 	file
 }
 
+// Save the contents of the contents vector to a new in-memory buffer
+fn save_buffer(contents: Vec<String>) -> FileBacking {
+	// Join the lines (the last line doesn't get a newline char)
+	let text = contents.join("\n");
+	// Create a new buffer from the text
+	FileBacking::from_bytes(text.into_bytes())
+}
+
+/* Compress the contents of the contents vector and save them to the given file.
+Returns an in-memory buffer of the uncompressed contents. */
+fn save_compressed(filename: &str, contents: Vec<String>, compression: Compression) -> FileBacking {
+	// Create a buffer of the uncompressed text
+	let mut buffer = save_buffer(contents);
+	let text = buffer.contents().unwrap();
+	// Compress the text
+	let compressed = match compression.compress(&text) {
+		Ok(compressed) => compressed,
+		Err(err) => panic!("{}", err),
+	};
+
+	// Write the compressed text to the file
+	let mut file = recreate_file(filename);
+	match file.write_all(&compressed) {
+		Ok(_) => (),
+		Err(err) => panic!("{}", err),
+	}
+	// Flush the file buffer
+	file.flush().unwrap();
+	// Return the uncompressed buffer
+	buffer
+}
+
 // Update the editor's scroll offset and blocks after saving
 fn post_save_editor_update(editor: &mut EditorSpace) -> Result<(), Error> {
 	// Get the current line number
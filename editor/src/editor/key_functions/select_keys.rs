@@ -0,0 +1,345 @@
+// Contains the logic for selecting the entire file, a word, or a line and expanding (or shrinking) the selection
+
+use super::{highlight_keys, mouse, multi_cursor, Blocks, EditorSpace, UnicodeSegmentation};
+
+// The pairs of brackets that a selection can expand through
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+// The quotes that a selection can expand through
+const QUOTES: [char; 3] = ['"', '\'', '`'];
+
+// A selection that was expanded (and the selection it was expanded from)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Expansion {
+	// The anchor and cursor (as [text position, line number]) before expanding
+	previous: [[usize; 2]; 2],
+	// The start and end of the expanded selection
+	expanded: [[usize; 2]; 2],
+}
+
+// The text around the selection (more blocks are loaded in as the search for a boundary reaches them)
+struct FileText {
+	// A copy of the editor's blocks (which loads in more blocks as needed)
+	blocks: Blocks,
+}
+
+// Select the entire file
+pub fn select_all(editor: &mut EditorSpace) {
+	multi_cursor::clear(editor);
+	highlight_keys::select_range(
+		editor,
+		[0, 0],
+		[usize::MAX, editor.file_length.saturating_sub(1)],
+	);
+}
+
+// Select the unicode word at the cursor
+pub fn select_word(editor: &mut EditorSpace) {
+	multi_cursor::clear(editor);
+	mouse::select_word(editor);
+}
+
+// Select the line at the cursor
+pub fn select_line(editor: &mut EditorSpace) {
+	multi_cursor::clear(editor);
+	mouse::select_line(editor);
+}
+
+/* Expand the selection outward to the next enclosing word, quotes, brackets,
+line, paragraph, or the entire file (whichever is the smallest). */
+pub fn expand_selection(editor: &mut EditorSpace) {
+	multi_cursor::clear(editor);
+	// The anchor and cursor of the current selection
+	let (anchor, cursor) = highlight_keys::selection_ends(editor);
+	// Forget the expansions if the selection was changed since the last one
+	if editor.selection.is_empty
+		|| editor.expansions.last().map(|expansion| expansion.expanded)
+			!= Some([editor.selection.start, editor.selection.end])
+	{
+		editor.expansions.clear();
+	}
+
+	// Find the smallest range of text that encloses the selection
+	let mut file = FileText::new(editor);
+	let [start, end] = match editor.selection.is_empty {
+		true => [file.clamp(cursor); 2],
+		false => [editor.selection.start, editor.selection.end],
+	};
+	match file.enclosing_range(editor, start, end) {
+		Some([new_start, new_end]) => highlight_keys::select_range(editor, new_start, new_end),
+		// Every line of the file is already selected
+		None if start == [0, 0] && end[1] + 1 >= editor.file_length => {
+			editor.set_status_message("The entire file is selected");
+			return;
+		}
+		// Otherwise, select the entire file
+		None => highlight_keys::select_range(
+			editor,
+			[0, 0],
+			[usize::MAX, editor.file_length.saturating_sub(1)],
+		),
+	}
+
+	// Remember the selection to shrink back to
+	editor.expansions.push(Expansion {
+		previous: [anchor, cursor],
+		expanded: [editor.selection.start, editor.selection.end],
+	});
+}
+
+// Shrink the selection back to what it was before it was last expanded
+pub fn shrink_selection(editor: &mut EditorSpace) {
+	// Only shrink a selection that hasn't been changed since it was expanded
+	let current = [editor.selection.start, editor.selection.end];
+	match editor.expansions.pop() {
+		Some(expansion) if !editor.selection.is_empty && expansion.expanded == current => {
+			let [anchor, cursor] = expansion.previous;
+			highlight_keys::select_range(editor, anchor, cursor);
+		}
+		_ => {
+			editor.expansions.clear();
+			editor.set_status_message("Nothing to shrink");
+		}
+	}
+}
+
+/*
+==============================
+			Helpers
+==============================
+*/
+
+impl FileText {
+	// Search a copy of the editor's blocks (so that loading more blocks doesn't move the view)
+	fn new(editor: &EditorSpace) -> Self {
+		FileText {
+			blocks: editor.blocks.as_ref().unwrap().clone(),
+		}
+	}
+
+	// Get a line of the file (loading in the blocks before or after the loaded blocks as needed)
+	fn line(&mut self, editor: &mut EditorSpace, line_num: usize) -> Option<String> {
+		while line_num < self.blocks.starting_line_num && self.blocks.head_block > 0 {
+			self.blocks
+				.push_head(editor, false)
+				.unwrap_or_else(|err| panic!("Couldn't load the previous block | {}", err));
+		}
+		while line_num >= self.blocks.starting_line_num + self.blocks.len()
+			&& self.blocks.tail_block + 1 < self.blocks.max_blocks
+		{
+			self.blocks
+				.push_tail(editor, false)
+				.unwrap_or_else(|err| panic!("Couldn't load the next block | {}", err));
+		}
+		self.blocks.get_some_line(line_num).ok()
+	}
+
+	// Keep a [text position, line number] within its (loaded) line
+	fn clamp(&self, [position, line_num]: [usize; 2]) -> [usize; 2] {
+		let line = self.blocks.get_some_line(line_num).unwrap_or_default();
+		[position.min(line.len()), line_num]
+	}
+
+	// Get the number of bytes in a range of loaded text (counting a newline between each line)
+	fn range_len(&self, [start, end]: [[usize; 2]; 2]) -> usize {
+		let line_len = |line_num| {
+			self.blocks
+				.get_some_line(line_num)
+				.unwrap_or_default()
+				.len()
+		};
+		match start[1] == end[1] {
+			true => end[0] - start[0],
+			false => {
+				(line_len(start[1]) - start[0])
+					+ (start[1] + 1..end[1])
+						.map(|line_num| line_len(line_num) + 1)
+						.sum::<usize>()
+					+ 1 + end[0]
+			}
+		}
+	}
+
+	// Get the smallest range of text that is larger than (and contains) the range from start to end
+	fn enclosing_range(
+		&mut self,
+		editor: &mut EditorSpace,
+		start: [usize; 2],
+		end: [usize; 2],
+	) -> Option<[[usize; 2]; 2]> {
+		// Every range that the selection could expand to (from the innermost outward)
+		let line = self.line(editor, start[1]).unwrap_or_default();
+		let mut ranges = Vec::new();
+		ranges.extend(word(&line, start));
+		ranges.extend(quotes(&line, start));
+		ranges.extend(self.brackets(editor, start, end));
+		ranges.extend(self.lines(editor, start, end));
+
+		// Keep the smallest range that encloses the selection (positions compare as [line, position])
+		let key = |[position, line_num]: [usize; 2]| (line_num, position);
+		ranges
+			.into_iter()
+			.filter(|[range_start, range_end]| {
+				key(*range_start) <= key(start)
+					&& key(end) <= key(*range_end)
+					&& [*range_start, *range_end] != [start, end]
+			})
+			.min_by_key(|range| self.range_len(*range))
+	}
+
+	/* Get the text inside of (and including) the brackets around the range from start to end.
+	Searches outward from the start until a pair of brackets is larger than the range. */
+	fn brackets(
+		&mut self,
+		editor: &mut EditorSpace,
+		start: [usize; 2],
+		end: [usize; 2],
+	) -> Vec<[[usize; 2]; 2]> {
+		let mut ranges = Vec::new();
+		// The opening brackets (of the closing brackets passed) that haven't been passed yet
+		let mut closed: Vec<char> = Vec::new();
+		let mut line_num = start[1];
+		while let Some(line) = self.line(editor, line_num) {
+			// The brackets before the start (and an opening bracket at it), from last to first
+			let brackets: Vec<(usize, char)> = line
+				.char_indices()
+				.filter(|(idx, ch)| match line_num == start[1] {
+					true => *idx < start[0] || (*idx == start[0] && is_open(*ch)),
+					false => true,
+				})
+				.filter(|(_, ch)| {
+					BRACKETS
+						.iter()
+						.any(|(open, close)| ch == open || ch == close)
+				})
+				.collect();
+
+			for (idx, ch) in brackets.into_iter().rev() {
+				if !is_open(ch) {
+					let (open, _) = BRACKETS.iter().find(|(_, close)| *close == ch).unwrap();
+					closed.push(*open);
+				} else if !closed.is_empty() {
+					// Skip an opening bracket that's closed before the start
+					if closed.last() == Some(&ch) {
+						closed.pop();
+					}
+				} else if let Some(close) = self.matching_close(editor, [idx, line_num]) {
+					// Keep the brackets that are around the range
+					let after = [close[0] + 1, close[1]];
+					if (end[1], end[0]) <= (after[1], after[0]) {
+						ranges.push([[idx + 1, line_num], close]);
+						ranges.push([[idx, line_num], after]);
+						if [[idx, line_num], after] != [start, end] {
+							return ranges;
+						}
+					}
+				}
+			}
+
+			// Move onto the previous line
+			match line_num {
+				0 => break,
+				_ => line_num -= 1,
+			}
+		}
+		ranges
+	}
+
+	// Get the position of the bracket that closes the opening bracket at a position
+	fn matching_close(
+		&mut self,
+		editor: &mut EditorSpace,
+		[position, line_num]: [usize; 2],
+	) -> Option<[usize; 2]> {
+		// The closing brackets that are still expected (starting with the one for the position)
+		let mut open: Vec<char> = Vec::new();
+		let mut current = line_num;
+		while let Some(line) = self.line(editor, current) {
+			for (idx, ch) in line.char_indices() {
+				if current == line_num && idx < position {
+					continue;
+				}
+				if let Some((_, close)) = BRACKETS.iter().find(|(open, _)| *open == ch) {
+					open.push(*close);
+				} else if open.last() == Some(&ch) {
+					open.pop();
+					if open.is_empty() {
+						return Some([idx, current]);
+					}
+				}
+			}
+			current += 1;
+		}
+		None
+	}
+
+	// Get the lines (and the paragraph) that the range from start to end is on
+	fn lines(
+		&mut self,
+		editor: &mut EditorSpace,
+		start: [usize; 2],
+		end: [usize; 2],
+	) -> Vec<[[usize; 2]; 2]> {
+		let (first, last) = (start[1], end[1]);
+		let last_len = self.line(editor, last).unwrap_or_default().len();
+		let lines = [[0, first], [last_len, last]];
+		let file_length = editor.file_length;
+
+		// The paragraph is surrounded by blank lines (or the start and end of the file)
+		let mut is_blank = |line_num: usize| {
+			self.line(editor, line_num)
+				.unwrap_or_default()
+				.trim()
+				.is_empty()
+		};
+		let mut top = first;
+		while top > 0 && !is_blank(top - 1) {
+			top -= 1;
+		}
+		let mut bottom = last;
+		while bottom + 1 < file_length && !is_blank(bottom + 1) {
+			bottom += 1;
+		}
+		let bottom_len = self.line(editor, bottom).unwrap_or_default().len();
+		vec![lines, [[0, top], [bottom_len, bottom]]]
+	}
+}
+
+// Check if a character is an opening bracket
+fn is_open(ch: char) -> bool {
+	BRACKETS.iter().any(|(open, _)| *open == ch)
+}
+
+// Get the unicode word at a position on its line
+fn word(line: &str, [position, line_num]: [usize; 2]) -> Option<[[usize; 2]; 2]> {
+	// The word containing the position (or before it at the end of the line)
+	line.split_word_bound_indices()
+		.map(|(idx, word)| (idx, idx + word.len()))
+		.find(|(start, end)| *start <= position && (position < *end || *end == line.len()))
+		.map(|(start, end)| [[start, line_num], [end, line_num]])
+}
+
+// Get the text inside of (and including) the quotes on a line
+fn quotes(line: &str, [_, line_num]: [usize; 2]) -> Vec<[[usize; 2]; 2]> {
+	// Pair up the quotes on the line (skipping escaped quotes)
+	let mut ranges = Vec::new();
+	let mut open: Option<(usize, char)> = None;
+	let mut chars = line.char_indices();
+	while let Some((idx, ch)) = chars.next() {
+		match (open, ch) {
+			// Skip the escaped character
+			(_, '\\') => {
+				chars.next();
+			}
+			// Close the open quote
+			(Some((start, quote)), _) if ch == quote => {
+				ranges.push([[start + 1, line_num], [idx, line_num]]);
+				ranges.push([[start, line_num], [idx + 1, line_num]]);
+				open = None;
+			}
+			// Open a new quote
+			(None, _) if QUOTES.contains(&ch) => open = Some((idx, ch)),
+			_ => (),
+		}
+	}
+	ranges
+}
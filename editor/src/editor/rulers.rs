@@ -0,0 +1,44 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::key_functions::display_width;
+
+/* Get the byte index of the first grapheme that is past the line length limit.
+Returns None if the entire line fits within the limit. */
+pub fn limit_index(line: &str, limit: usize, tab_width: usize) -> Option<usize> {
+	// The display column of the current grapheme
+	let mut column = 0;
+	for (idx, grapheme) in line.grapheme_indices(true) {
+		column += display_width(grapheme, tab_width);
+		// The grapheme ends past the limit
+		if column > limit {
+			return Some(idx);
+		}
+	}
+	None
+}
+
+/* Draw the rulers as background colored columns of the text area.
+The columns are shifted by the horizontal offset (and the selection is drawn over them). */
+pub fn draw_rulers(
+	buffer: &mut Buffer,
+	area: Rect,
+	rulers: &[usize],
+	offset: usize,
+	color: Color,
+	selection: Color,
+) {
+	// Only draw the rulers that are visible in the text area
+	let columns = rulers
+		.iter()
+		.filter(|column| **column >= offset && **column - offset < area.width as usize);
+	for column in columns {
+		let x = area.x + (column - offset) as u16;
+		for y in area.y..area.y + area.height {
+			let cell = buffer.get_mut(x, y);
+			if cell.bg != selection {
+				cell.set_bg(color);
+			}
+		}
+	}
+}
@@ -0,0 +1,96 @@
+use std::mem::take;
+
+use ratatui::text::{Line, Span};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/* Calculate where each visual row of a soft wrapped line starts.
+Returns a (byte index, display column) pair for the start of each row.
+Lines are wrapped at word boundaries unless a word is wider than the widget. */
+pub fn wrap_points(line: &str, width: usize, tab_width: usize) -> Vec<(usize, usize)> {
+	// The first row always starts at the beginning of the line
+	let mut points = vec![(0, 0)];
+	// Can't wrap to a width of 0
+	if width == 0 {
+		return points;
+	}
+
+	// The start of the most recent word (a possible place to wrap)
+	let mut word_start = (0, 0);
+	// Flag tracking whether the previous grapheme was whitespace
+	let mut prev_whitespace = false;
+	// The display column of the current grapheme
+	let mut column = 0;
+
+	for (idx, grapheme) in line.grapheme_indices(true) {
+		// Display width of the grapheme (tabs are tab_width wide)
+		let grapheme_width = match grapheme {
+			"\t" => tab_width,
+			_ => UnicodeWidthStr::width(grapheme),
+		};
+		let is_whitespace = grapheme.chars().all(char::is_whitespace);
+		// A new word starts after whitespace
+		if prev_whitespace && !is_whitespace {
+			word_start = (idx, column);
+		}
+
+		// Wrap while the grapheme doesn't fit on the current row
+		while column + grapheme_width > points.last().unwrap().1 + width
+			&& column > points.last().unwrap().1
+		{
+			// Wrap before the current word if it doesn't start the row
+			if !is_whitespace && word_start.1 > points.last().unwrap().1 {
+				points.push(word_start);
+			// Otherwise, break the row at this grapheme
+			} else {
+				points.push((idx, column));
+			}
+		}
+
+		column += grapheme_width;
+		prev_whitespace = is_whitespace;
+	}
+
+	points
+}
+
+// Get the index of the visual row that the given byte index is on
+pub fn row_of(points: &[(usize, usize)], text_position: usize) -> usize {
+	points
+		.iter()
+		.rposition(|(idx, _)| *idx <= text_position)
+		.unwrap_or(0)
+}
+
+/* Split a rendered line into visual rows at the display columns of the wrap points.
+The rendered line must have the same display width as the text line (i.e. tabs
+expanded to tab_width). */
+pub fn split_line<'a>(line: Line<'a>, points: &[(usize, usize)]) -> Vec<Line<'a>> {
+	// Style of the entire line
+	let style = line.style;
+	// Display columns where a new row starts
+	let mut boundaries = points.iter().skip(1).map(|(_, column)| *column);
+	let mut next_boundary = boundaries.next();
+
+	let mut rows = Vec::new();
+	let mut row: Vec<Span> = Vec::new();
+	let mut column = 0;
+	for span in line.spans {
+		// Text of the span on the current row
+		let mut text = String::new();
+		for grapheme in span.content.graphemes(true) {
+			// Start a new row at each boundary
+			while next_boundary.is_some_and(|boundary| column >= boundary) {
+				row.push(Span::styled(take(&mut text), span.style));
+				rows.push(Line::from(take(&mut row)).style(style));
+				next_boundary = boundaries.next();
+			}
+			text.push_str(grapheme);
+			column += UnicodeWidthStr::width(grapheme);
+		}
+		row.push(Span::styled(text, span.style));
+	}
+	rows.push(Line::from(row).style(style));
+
+	rows
+}
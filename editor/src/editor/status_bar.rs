@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+use ratatui::text::{Line, Span};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+// How long a message is shown in the status bar
+const MESSAGE_DURATION: Duration = Duration::from_secs(5);
+
+// A transient message shown in the status bar
+#[derive(Clone, Debug)]
+pub struct StatusMessage {
+	pub text: String,
+	// When the message was set
+	pub time: Instant,
+}
+
+impl StatusMessage {
+	// Create a new message (shown from now)
+	pub fn new(text: String) -> Self {
+		StatusMessage {
+			text,
+			time: Instant::now(),
+		}
+	}
+
+	// Check if the message should still be shown
+	pub fn is_visible(&self) -> bool {
+		self.time.elapsed() < MESSAGE_DURATION
+	}
+}
+
+// Get the name of the encoding of a file's bytes
+pub fn detect_encoding(bytes: &[u8]) -> &'static str {
+	if bytes.starts_with(b"\xEF\xBB\xBF") {
+		"UTF-8 BOM"
+	} else if std::str::from_utf8(bytes).is_ok() {
+		"UTF-8"
+	// Invalid bytes are replaced when the file is read
+	} else {
+		"UTF-8 (lossy)"
+	}
+}
+
+// Get the name of the most common line ending in some text
+pub fn detect_line_ending(text: &str) -> &'static str {
+	// The number of lines ending in "\r\n"
+	let crlf = text.matches("\r\n").count();
+	match crlf > 0 && crlf * 2 >= text.matches('\n').count() {
+		true => "CRLF",
+		false => "LF",
+	}
+}
+
+/* Build the line of the status bar with the left text at the start and the
+right text at the end. The left text is cut off if there isn't enough room. */
+pub fn build_line<'a>(left: Vec<Span<'a>>, right: String, width: usize) -> Line<'a> {
+	// The room left over for the left text
+	let mut room = width.saturating_sub(UnicodeWidthStr::width(right.as_str()));
+
+	let mut spans = Vec::new();
+	for span in left {
+		// Only keep the graphemes of the span that fit
+		let mut text = String::new();
+		for grapheme in span.content.graphemes(true) {
+			let grapheme_width = UnicodeWidthStr::width(grapheme);
+			if grapheme_width > room {
+				room = 0;
+				break;
+			}
+			text.push_str(grapheme);
+			room -= grapheme_width;
+		}
+		spans.push(Span::styled(text, span.style));
+	}
+
+	// Fill the space between the left and right text
+	spans.push(Span::from(" ".repeat(room)));
+	spans.push(Span::from(right));
+
+	Line::from(spans)
+}
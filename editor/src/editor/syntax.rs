@@ -0,0 +1,136 @@
+use config::config::theme::Theme;
+use ratatui::style::{Color, Style};
+
+// Grammar definitions of the languages highlighted by the generic lexer
+mod grammars;
+// The supported languages (and detecting the language of a file)
+mod language;
+pub use language::Language;
+// Generic lexer that tokenizes a line using a grammar definition
+mod lexer;
+// Line based highlighter for Markdown
+mod markdown;
+// Syntax trees parsed with the compiled in tree-sitter grammars
+#[cfg(feature = "tree-sitter")]
+mod tree;
+#[cfg(feature = "tree-sitter")]
+pub use tree::{SyntaxNode, SyntaxTree};
+
+// The kind of a highlighted token (each kind has its own color in the Theme)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TokenKind {
+	Keyword,
+	Type,
+	Function,
+	// Attributes, macros, decorators, lifetimes, and variables
+	Attribute,
+	String,
+	Number,
+	Constant,
+	Comment,
+	Key,
+	Heading,
+	Emphasis,
+	Link,
+}
+
+impl TokenKind {
+	// Get the color of this kind of token from the theme
+	pub fn color(self, theme: &Theme) -> Color {
+		match self {
+			TokenKind::Keyword => theme.syntax_keyword,
+			TokenKind::Type => theme.syntax_type,
+			TokenKind::Function => theme.syntax_function,
+			TokenKind::Attribute => theme.syntax_attribute,
+			TokenKind::String => theme.syntax_string,
+			TokenKind::Number => theme.syntax_number,
+			TokenKind::Constant => theme.syntax_constant,
+			TokenKind::Comment => theme.syntax_comment,
+			TokenKind::Key => theme.syntax_key,
+			TokenKind::Heading => theme.syntax_heading,
+			TokenKind::Emphasis => theme.syntax_emphasis,
+			TokenKind::Link => theme.syntax_link,
+		}
+	}
+}
+
+// A highlighted range of bytes within a line
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Token {
+	// Byte index of the start of the token
+	pub start: usize,
+	// Byte index after the end of the token
+	pub end: usize,
+	pub kind: TokenKind,
+}
+
+// State carried from the end of one line to the start of the next (for multi-line constructs)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SyntaxState {
+	#[default]
+	Normal,
+	// Inside of a block comment (with its nesting depth)
+	BlockComment(usize),
+	// Inside of a string (with the index of its delimiter in the grammar)
+	String(usize),
+	// Inside of a fenced code block (Markdown)
+	CodeFence,
+}
+
+/* The highlighting of a single line. These are cached per line in each TextBlock
+and only recalculated when the line is edited (or the state it starts in changes). */
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineHighlight {
+	// The state at the start of the line
+	pub start_state: SyntaxState,
+	// The highlighted tokens of the line (in order)
+	pub tokens: Vec<Token>,
+	// The state at the end of the line (the start state of the next line)
+	pub end_state: SyntaxState,
+}
+
+// Highlight a line of text in the given language, starting in the given state
+pub fn highlight_line(language: Language, line: &str, state: SyntaxState) -> LineHighlight {
+	let (tokens, end_state) = match language.grammar() {
+		Some(grammar) => lexer::tokenize(grammar, line, state),
+		None => markdown::tokenize(line, state),
+	};
+
+	LineHighlight {
+		start_state: state,
+		tokens,
+		end_state,
+	}
+}
+
+// Split a line into segments of text with the kind of token they are (if any)
+pub fn segments<'a>(line: &'a str, tokens: &[Token]) -> Vec<(&'a str, Option<TokenKind>)> {
+	let mut segments = Vec::new();
+	// The end of the previous token
+	let mut position = 0;
+	for token in tokens {
+		// Unhighlighted text before the token
+		if token.start > position {
+			segments.push((&line[position..token.start], None));
+		}
+		segments.push((&line[token.start..token.end], Some(token.kind)));
+		position = token.end;
+	}
+	// Unhighlighted text after the last token
+	if position < line.len() {
+		segments.push((&line[position..], None));
+	}
+
+	segments
+}
+
+// Get the style of the text at the given byte index of a highlighted line
+pub fn style_at(tokens: &[Token], loc: usize, theme: &Theme) -> Style {
+	match tokens
+		.iter()
+		.find(|token| token.start <= loc && loc < token.end)
+	{
+		Some(token) => Style::default().fg(token.kind.color(theme)),
+		None => Style::default(),
+	}
+}
@@ -0,0 +1,333 @@
+// Definition of the syntax of a language that is tokenized by the generic lexer
+pub struct Grammar {
+	// Reserved words of the language
+	pub keywords: &'static [&'static str],
+	// Names of built in types
+	pub types: &'static [&'static str],
+	// Names of built in constants
+	pub constants: &'static [&'static str],
+	// Start of a comment that continues to the end of the line
+	pub line_comment: Option<&'static str>,
+	// Flag for line comments only starting at the beginning of a word (e.g. in shell)
+	pub comment_needs_space: bool,
+	// Start and end of a block comment (which can span lines)
+	pub block_comment: Option<(&'static str, &'static str)>,
+	// Flag for block comments being nestable
+	pub nested_comments: bool,
+	// Delimiters of strings (longer delimiters must come first)
+	pub strings: &'static [StringDelimiter],
+	// Prefixes directly before a string that are part of it (e.g. r"" or f"")
+	pub string_prefixes: &'static [&'static str],
+	// Prefixes of attributes or decorators (e.g. #[ in Rust or @ in Python)
+	pub attribute_prefixes: &'static [&'static str],
+	// Prefix of variables (e.g. $ in shell)
+	pub variable_prefix: Option<char>,
+	// Character between a key and its value (e.g. : in JSON)
+	pub key_separator: Option<char>,
+	// Flag for tables in square brackets at the start of a line (e.g. in TOML)
+	pub table_headers: bool,
+	// Flag for identifiers followed by ! being macros
+	pub macros: bool,
+	// Flag for single quotes being char literals or lifetimes (instead of strings)
+	pub char_literals: bool,
+	// Flag for capitalized identifiers being types (e.g. struct or class names)
+	pub capitalized_types: bool,
+	// Flag for keywords, types, and constants matching in any case (e.g. in SQL)
+	pub ignore_case: bool,
+}
+
+// The delimiters of a string
+pub struct StringDelimiter {
+	pub open: &'static str,
+	pub close: &'static str,
+	// Flag for backslashes escaping the next character
+	pub escapes: bool,
+	// Flag for the string being able to span multiple lines
+	pub multiline: bool,
+}
+
+// A grammar with nothing defined (used as a base for the other grammars)
+const EMPTY: Grammar = Grammar {
+	keywords: &[],
+	types: &[],
+	constants: &[],
+	line_comment: None,
+	comment_needs_space: false,
+	block_comment: None,
+	nested_comments: false,
+	strings: &[],
+	string_prefixes: &[],
+	attribute_prefixes: &[],
+	variable_prefix: None,
+	key_separator: None,
+	table_headers: false,
+	macros: false,
+	char_literals: false,
+	capitalized_types: false,
+	ignore_case: false,
+};
+
+pub const RUST: Grammar = Grammar {
+	keywords: &[
+		"as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+		"extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+		"pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+		"unsafe", "use", "where", "while", "yield",
+	],
+	types: &[
+		"bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+		"i64", "i128", "isize", "f32", "f64",
+	],
+	constants: &["true", "false", "None", "Some", "Ok", "Err"],
+	line_comment: Some("//"),
+	block_comment: Some(("/*", "*/")),
+	nested_comments: true,
+	strings: &[
+		// The end of raw strings (r#"..."#)
+		StringDelimiter {
+			open: "#\"",
+			close: "\"#",
+			escapes: false,
+			multiline: true,
+		},
+		StringDelimiter {
+			open: "\"",
+			close: "\"",
+			escapes: true,
+			multiline: true,
+		},
+	],
+	string_prefixes: &["br", "b", "r"],
+	attribute_prefixes: &["#![", "#["],
+	macros: true,
+	char_literals: true,
+	capitalized_types: true,
+	..EMPTY
+};
+
+pub const TOML: Grammar = Grammar {
+	constants: &["true", "false", "inf", "nan"],
+	line_comment: Some("#"),
+	strings: &[
+		StringDelimiter {
+			open: "\"\"\"",
+			close: "\"\"\"",
+			escapes: true,
+			multiline: true,
+		},
+		StringDelimiter {
+			open: "'''",
+			close: "'''",
+			escapes: false,
+			multiline: true,
+		},
+		StringDelimiter {
+			open: "\"",
+			close: "\"",
+			escapes: true,
+			multiline: false,
+		},
+		StringDelimiter {
+			open: "'",
+			close: "'",
+			escapes: false,
+			multiline: false,
+		},
+	],
+	key_separator: Some('='),
+	table_headers: true,
+	..EMPTY
+};
+
+pub const PYTHON: Grammar = Grammar {
+	keywords: &[
+		"and", "as", "assert", "async", "await", "break", "case", "class", "continue", "def",
+		"del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in",
+		"is", "lambda", "match", "nonlocal", "not", "or", "pass", "raise", "return", "try",
+		"while", "with", "yield",
+	],
+	types: &[
+		"bool",
+		"bytes",
+		"complex",
+		"dict",
+		"float",
+		"frozenset",
+		"int",
+		"list",
+		"object",
+		"set",
+		"str",
+		"tuple",
+		"type",
+	],
+	constants: &["True", "False", "None"],
+	line_comment: Some("#"),
+	strings: &[
+		StringDelimiter {
+			open: "\"\"\"",
+			close: "\"\"\"",
+			escapes: true,
+			multiline: true,
+		},
+		StringDelimiter {
+			open: "'''",
+			close: "'''",
+			escapes: true,
+			multiline: true,
+		},
+		StringDelimiter {
+			open: "\"",
+			close: "\"",
+			escapes: true,
+			multiline: false,
+		},
+		StringDelimiter {
+			open: "'",
+			close: "'",
+			escapes: true,
+			multiline: false,
+		},
+	],
+	string_prefixes: &["rb", "br", "fr", "rf", "b", "r", "f", "u"],
+	attribute_prefixes: &["@"],
+	capitalized_types: true,
+	..EMPTY
+};
+
+pub const SHELL: Grammar = Grammar {
+	keywords: &[
+		"if", "then", "else", "elif", "fi", "case", "esac", "for", "select", "while", "until",
+		"do", "done", "in", "function", "time", "return", "break", "continue", "exit", "local",
+		"export", "readonly", "declare", "unset", "shift", "source", "alias",
+	],
+	line_comment: Some("#"),
+	comment_needs_space: true,
+	strings: &[
+		StringDelimiter {
+			open: "\"",
+			close: "\"",
+			escapes: true,
+			multiline: true,
+		},
+		StringDelimiter {
+			open: "'",
+			close: "'",
+			escapes: false,
+			multiline: true,
+		},
+	],
+	variable_prefix: Some('$'),
+	..EMPTY
+};
+
+pub const JSON: Grammar = Grammar {
+	constants: &["true", "false", "null"],
+	strings: &[StringDelimiter {
+		open: "\"",
+		close: "\"",
+		escapes: true,
+		multiline: false,
+	}],
+	key_separator: Some(':'),
+	..EMPTY
+};
+
+pub const SQL: Grammar = Grammar {
+	keywords: &[
+		"add",
+		"all",
+		"alter",
+		"and",
+		"as",
+		"asc",
+		"begin",
+		"between",
+		"by",
+		"case",
+		"check",
+		"column",
+		"commit",
+		"constraint",
+		"create",
+		"cross",
+		"default",
+		"delete",
+		"desc",
+		"distinct",
+		"drop",
+		"else",
+		"end",
+		"exists",
+		"foreign",
+		"from",
+		"full",
+		"group",
+		"having",
+		"if",
+		"in",
+		"index",
+		"inner",
+		"insert",
+		"into",
+		"is",
+		"join",
+		"key",
+		"left",
+		"like",
+		"limit",
+		"not",
+		"offset",
+		"on",
+		"or",
+		"order",
+		"outer",
+		"primary",
+		"references",
+		"right",
+		"rollback",
+		"select",
+		"set",
+		"table",
+		"then",
+		"transaction",
+		"union",
+		"unique",
+		"update",
+		"values",
+		"view",
+		"when",
+		"where",
+		"with",
+	],
+	types: &[
+		"bigint",
+		"blob",
+		"boolean",
+		"char",
+		"date",
+		"decimal",
+		"double",
+		"float",
+		"int",
+		"integer",
+		"numeric",
+		"real",
+		"smallint",
+		"text",
+		"time",
+		"timestamp",
+		"varchar",
+	],
+	constants: &["true", "false", "null"],
+	line_comment: Some("--"),
+	block_comment: Some(("/*", "*/")),
+	strings: &[StringDelimiter {
+		open: "'",
+		close: "'",
+		escapes: false,
+		multiline: true,
+	}],
+	ignore_case: true,
+	..EMPTY
+};
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use super::grammars::{self, Grammar};
+
+// The languages that can be syntax highlighted
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+	Rust,
+	Toml,
+	Markdown,
+	Python,
+	Shell,
+	Json,
+	Sql,
+}
+
+impl Language {
+	// Detect the language of a file from its name (or the shebang on its first line)
+	pub fn detect(filename: &str, first_line: &str) -> Option<Self> {
+		Self::from_filename(filename).or_else(|| Self::from_shebang(first_line))
+	}
+
+	// Get the language from the name (or extension) of a file
+	fn from_filename(filename: &str) -> Option<Self> {
+		let path = Path::new(filename);
+		// Ignore the extension of compressed files (e.g. main.rs.gz)
+		let path = match path.extension().and_then(|extension| extension.to_str()) {
+			Some("gz" | "zst") => Path::new(path.file_stem()?),
+			_ => path,
+		};
+
+		// Some files are recognized by their entire name
+		match path.file_name()?.to_str()? {
+			"Cargo.lock" => return Some(Language::Toml),
+			".bashrc" | ".bash_profile" | ".bash_logout" | ".profile" | ".zshrc" | ".zprofile" => {
+				return Some(Language::Shell)
+			}
+			_ => (),
+		}
+
+		match path.extension()?.to_str()? {
+			"rs" => Some(Language::Rust),
+			"toml" => Some(Language::Toml),
+			"md" | "markdown" => Some(Language::Markdown),
+			"py" | "pyw" | "pyi" => Some(Language::Python),
+			"sh" | "bash" | "zsh" | "ksh" => Some(Language::Shell),
+			"json" => Some(Language::Json),
+			"sql" => Some(Language::Sql),
+			_ => None,
+		}
+	}
+
+	// Get the language from the interpreter in a shebang (e.g. #!/usr/bin/env python3)
+	fn from_shebang(first_line: &str) -> Option<Self> {
+		let mut words = first_line.strip_prefix("#!")?.split_whitespace();
+		// The name of the interpreter (without its directory)
+		let mut interpreter = words.next()?.rsplit('/').next()?;
+		// The actual interpreter follows env (skipping any flags passed to env)
+		if interpreter == "env" {
+			interpreter = words.find(|word| !word.starts_with('-'))?;
+		}
+
+		// Ignore the version of the interpreter (e.g. python3.12)
+		match interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+			"python" => Some(Language::Python),
+			"sh" | "bash" | "zsh" | "ksh" | "dash" | "ash" => Some(Language::Shell),
+			_ => None,
+		}
+	}
+
+	// Get the name of the language
+	pub fn name(self) -> &'static str {
+		match self {
+			Language::Rust => "Rust",
+			Language::Toml => "TOML",
+			Language::Markdown => "Markdown",
+			Language::Python => "Python",
+			Language::Shell => "Shell",
+			Language::Json => "JSON",
+			Language::Sql => "SQL",
+		}
+	}
+
+	// Get the token that starts a line comment (if the language has line comments)
+	pub fn line_comment(self) -> Option<&'static str> {
+		match self {
+			Language::Rust => Some("//"),
+			Language::Toml | Language::Python | Language::Shell => Some("#"),
+			Language::Sql => Some("--"),
+			Language::Markdown | Language::Json => None,
+		}
+	}
+
+	// Get the tokens that start and end a block comment (if the language has block comments)
+	pub fn block_comment(self) -> Option<(&'static str, &'static str)> {
+		match self {
+			Language::Rust | Language::Sql => Some(("/*", "*/")),
+			Language::Markdown => Some(("<!--", "-->")),
+			_ => None,
+		}
+	}
+
+	// Get the characters that indent the next line when they end a line
+	pub fn indent_openers(self) -> &'static [char] {
+		match self {
+			Language::Python => &['(', '[', '{', ':'],
+			Language::Markdown => &[],
+			_ => &['(', '[', '{'],
+		}
+	}
+
+	// Get the grammar used to tokenize the language (Markdown has its own highlighter)
+	pub fn grammar(self) -> Option<&'static Grammar> {
+		match self {
+			Language::Rust => Some(&grammars::RUST),
+			Language::Toml => Some(&grammars::TOML),
+			Language::Markdown => None,
+			Language::Python => Some(&grammars::PYTHON),
+			Language::Shell => Some(&grammars::SHELL),
+			Language::Json => Some(&grammars::JSON),
+			Language::Sql => Some(&grammars::SQL),
+		}
+	}
+}
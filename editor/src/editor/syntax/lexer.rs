@@ -0,0 +1,398 @@
+use super::{grammars::Grammar, SyntaxState, Token, TokenKind};
+
+// Tokenize a line using the given grammar, starting in the given state
+pub fn tokenize(grammar: &Grammar, line: &str, state: SyntaxState) -> (Vec<Token>, SyntaxState) {
+	let mut lexer = Lexer {
+		grammar,
+		line,
+		position: 0,
+		tokens: Vec::new(),
+		state,
+	};
+	lexer.run();
+
+	(lexer.tokens, lexer.state)
+}
+
+// Check if a character can be part of an identifier
+fn is_ident_char(character: char) -> bool {
+	character.is_alphanumeric() || character == '_'
+}
+
+// Tracks the progress of tokenizing a line
+struct Lexer<'a> {
+	grammar: &'a Grammar,
+	line: &'a str,
+	// Byte index of the next character to tokenize
+	position: usize,
+	// The tokens found so far
+	tokens: Vec<Token>,
+	// The current state (carried to the next line at the end)
+	state: SyntaxState,
+}
+
+impl<'a> Lexer<'a> {
+	// Tokenize the entire line
+	fn run(&mut self) {
+		// Finish any construct that continues from the previous line
+		match self.state {
+			SyntaxState::BlockComment(depth) => self.block_comment(0, depth),
+			SyntaxState::String(idx) => self.string(0, idx),
+			_ => (),
+		}
+
+		while self.position < self.line.len() {
+			// Skip over any character that doesn't start a token
+			if !self.next_token() {
+				self.position += self.rest().chars().next().unwrap().len_utf8();
+			}
+		}
+	}
+
+	// The rest of the line that hasn't been tokenized
+	fn rest(&self) -> &'a str {
+		&self.line[self.position..]
+	}
+
+	// Add a token to the list (ignoring empty tokens)
+	fn push(&mut self, start: usize, end: usize, kind: TokenKind) {
+		if end > start {
+			self.tokens.push(Token { start, end, kind });
+		}
+	}
+
+	// Check if the text at the given index is followed by a key separator
+	fn is_key(&self, end: usize) -> bool {
+		self.grammar
+			.key_separator
+			.is_some_and(|separator| self.line[end..].trim_start().starts_with(separator))
+	}
+
+	// Tokenize the token at the current position (returns false if there isn't one)
+	fn next_token(&mut self) -> bool {
+		let (start, rest) = (self.position, self.rest());
+		// The character before the current position
+		let previous = self.line[..start].chars().next_back();
+
+		// Comments that continue to the end of the line
+		if let Some(comment) = self.grammar.line_comment {
+			if rest.starts_with(comment)
+				&& (!self.grammar.comment_needs_space
+					|| previous.filter(|c| !c.is_whitespace()).is_none())
+			{
+				self.push(start, self.line.len(), TokenKind::Comment);
+				self.position = self.line.len();
+				return true;
+			}
+		}
+		// Block comments
+		if let Some((open, _)) = self.grammar.block_comment {
+			if rest.starts_with(open) {
+				self.position += open.len();
+				self.block_comment(start, 1);
+				return true;
+			}
+		}
+		// Attributes and decorators
+		if let Some(prefix) = self
+			.grammar
+			.attribute_prefixes
+			.iter()
+			.find(|prefix| rest.starts_with(**prefix))
+		{
+			self.attribute(start, prefix);
+			return true;
+		}
+		// Variables
+		if self
+			.grammar
+			.variable_prefix
+			.is_some_and(|prefix| rest.starts_with(prefix))
+		{
+			self.variable(start);
+			return true;
+		}
+		// Table headers at the start of a line
+		if self.grammar.table_headers
+			&& rest.starts_with('[')
+			&& self.line[..start].trim().is_empty()
+		{
+			// Arrays of tables are surrounded by double brackets
+			let close = match rest.starts_with("[[") {
+				true => "]]",
+				false => "]",
+			};
+			self.position = match rest.find(close) {
+				Some(idx) => start + idx + close.len(),
+				None => self.line.len(),
+			};
+			self.push(start, self.position, TokenKind::Type);
+			return true;
+		}
+		// Strings
+		if self.string_start(start, previous) {
+			return true;
+		}
+		// Char literals and lifetimes
+		if self.grammar.char_literals && rest.starts_with('\'') {
+			self.char_literal(start);
+			return true;
+		}
+
+		let character = rest.chars().next().unwrap();
+		// Numbers
+		if character.is_ascii_digit() {
+			self.number(start);
+			return true;
+		}
+		// Keywords, types, constants, functions, and so on
+		if is_ident_char(character) {
+			self.identifier(start);
+			return true;
+		}
+
+		false
+	}
+
+	// Tokenize a block comment (until the end of the comment or line)
+	fn block_comment(&mut self, start: usize, mut depth: usize) {
+		let (open, close) = self.grammar.block_comment.unwrap();
+		while self.position < self.line.len() {
+			let rest = self.rest();
+			// The end of a (possibly nested) comment
+			if rest.starts_with(close) {
+				self.position += close.len();
+				depth -= 1;
+				if depth == 0 {
+					self.push(start, self.position, TokenKind::Comment);
+					self.state = SyntaxState::Normal;
+					return;
+				}
+			// The start of a nested comment
+			} else if self.grammar.nested_comments && rest.starts_with(open) {
+				self.position += open.len();
+				depth += 1;
+			} else {
+				self.position += rest.chars().next().unwrap().len_utf8();
+			}
+		}
+
+		// The comment continues onto the next line
+		self.push(start, self.line.len(), TokenKind::Comment);
+		self.state = SyntaxState::BlockComment(depth);
+	}
+
+	// Check for the start of a string (with an optional prefix) and tokenize it
+	fn string_start(&mut self, start: usize, previous: Option<char>) -> bool {
+		// Prefixes can't be part of a longer identifier
+		let prefixes = match previous.is_some_and(is_ident_char) {
+			true => &[][..],
+			false => self.grammar.string_prefixes,
+		};
+		let rest = self.rest();
+
+		for prefix in std::iter::once(&"").chain(prefixes) {
+			// Prefixes aren't case sensitive (e.g. R"" and r"" in Python)
+			if !rest
+				.get(..prefix.len())
+				.is_some_and(|text| text.eq_ignore_ascii_case(prefix))
+			{
+				continue;
+			}
+			let after_prefix = &rest[prefix.len()..];
+			if let Some(idx) = self
+				.grammar
+				.strings
+				.iter()
+				.position(|delimiter| after_prefix.starts_with(delimiter.open))
+			{
+				self.position += prefix.len() + self.grammar.strings[idx].open.len();
+				self.string(start, idx);
+				return true;
+			}
+		}
+
+		false
+	}
+
+	// Tokenize the rest of a string (until its closing delimiter or the end of the line)
+	fn string(&mut self, start: usize, idx: usize) {
+		let delimiter = &self.grammar.strings[idx];
+		let mut characters = self.rest().char_indices();
+		while let Some((offset, character)) = characters.next() {
+			// Skip over escaped characters
+			if delimiter.escapes && character == '\\' {
+				characters.next();
+				continue;
+			}
+			// The end of the string
+			if self.rest()[offset..].starts_with(delimiter.close) {
+				let end = self.position + offset + delimiter.close.len();
+				// Strings followed by a key separator are keys (e.g. in JSON)
+				let kind = match self.is_key(end) {
+					true => TokenKind::Key,
+					false => TokenKind::String,
+				};
+				self.push(start, end, kind);
+				self.position = end;
+				self.state = SyntaxState::Normal;
+				return;
+			}
+		}
+
+		// The string continues to the end of the line (and onto the next if it can)
+		self.push(start, self.line.len(), TokenKind::String);
+		self.position = self.line.len();
+		self.state = match delimiter.multiline {
+			true => SyntaxState::String(idx),
+			false => SyntaxState::Normal,
+		};
+	}
+
+	// Tokenize a char literal (e.g. 'a' or '\n') or a lifetime (e.g. 'a)
+	fn char_literal(&mut self, start: usize) {
+		let rest = self.rest();
+		let mut characters = rest.char_indices().skip(1);
+		let end = match (characters.next(), characters.next()) {
+			// Escaped characters end at the next quote
+			(Some((_, '\\')), _) => rest[2..].find('\'').map(|idx| start + idx + 3),
+			// A single character between quotes
+			(Some(_), Some((idx, '\''))) => Some(start + idx + 1),
+			_ => None,
+		};
+
+		match end {
+			Some(end) => {
+				self.push(start, end, TokenKind::String);
+				self.position = end;
+			}
+			// Otherwise, it's a lifetime (or label)
+			None => {
+				let length = rest[1..]
+					.find(|character| !is_ident_char(character))
+					.unwrap_or(rest.len() - 1);
+				self.position += length + 1;
+				self.push(start, self.position, TokenKind::Attribute);
+			}
+		}
+	}
+
+	// Tokenize an attribute (e.g. #[derive(Debug)]) or decorator (e.g. @property)
+	fn attribute(&mut self, start: usize, prefix: &str) {
+		self.position += prefix.len();
+		// Attributes in brackets end at the matching bracket
+		if prefix.ends_with('[') {
+			let mut depth = 1;
+			for (idx, character) in self.rest().char_indices() {
+				match character {
+					'[' => depth += 1,
+					']' => depth -= 1,
+					_ => (),
+				}
+				if depth == 0 {
+					self.position += idx + 1;
+					self.push(start, self.position, TokenKind::Attribute);
+					return;
+				}
+			}
+			self.position = self.line.len();
+		// Decorators are a (dotted) name
+		} else {
+			self.position += self
+				.rest()
+				.find(|character| !is_ident_char(character) && character != '.')
+				.unwrap_or(self.rest().len());
+			// A prefix on its own isn't a decorator (e.g. the @ operator)
+			if self.position == start + prefix.len() {
+				return;
+			}
+		}
+		self.push(start, self.position, TokenKind::Attribute);
+	}
+
+	// Tokenize a variable (e.g. $HOME, ${HOME}, or $1)
+	fn variable(&mut self, start: usize) {
+		// Skip over the prefix
+		self.position += 1;
+		let rest = self.rest();
+		let length = match rest.chars().next() {
+			// Variables in braces end at the closing brace
+			Some('{') => rest.find('}').map_or(rest.len(), |idx| idx + 1),
+			Some(character) if is_ident_char(character) => rest
+				.find(|character| !is_ident_char(character))
+				.unwrap_or(rest.len()),
+			// Special variables (e.g. $?, $#, or $@)
+			Some('#' | '?' | '@' | '*' | '$' | '!' | '-') => 1,
+			_ => return,
+		};
+		self.position += length;
+		self.push(start, self.position, TokenKind::Attribute);
+	}
+
+	// Tokenize a number (including decimals, suffixes, and other bases)
+	fn number(&mut self, start: usize) {
+		let rest = self.rest();
+		let mut characters = rest.char_indices().peekable();
+		let mut end = rest.len();
+		while let Some((idx, character)) = characters.next() {
+			// Decimal points must be followed by a digit (e.g. not a range like 0..10)
+			let is_decimal = character == '.'
+				&& characters
+					.peek()
+					.is_some_and(|(_, next)| next.is_ascii_digit());
+			if !is_ident_char(character) && !is_decimal {
+				end = idx;
+				break;
+			}
+		}
+		self.position += end;
+		self.push(start, self.position, TokenKind::Number);
+	}
+
+	// Tokenize an identifier (e.g. a keyword, type, or function name)
+	fn identifier(&mut self, start: usize) {
+		self.position += self
+			.rest()
+			.find(|character| !is_ident_char(character))
+			.unwrap_or(self.rest().len());
+		let (word, after) = (&self.line[start..self.position], self.rest());
+		// Check if the word is in a list of words (in any case if the grammar ignores case)
+		let ignore_case = self.grammar.ignore_case;
+		let is_in = |words: &[&str]| {
+			words
+				.iter()
+				.any(|known| *known == word || (ignore_case && known.eq_ignore_ascii_case(word)))
+		};
+
+		let kind = if is_in(self.grammar.keywords) {
+			Some(TokenKind::Keyword)
+		} else if is_in(self.grammar.constants) {
+			Some(TokenKind::Constant)
+		} else if is_in(self.grammar.types) {
+			Some(TokenKind::Type)
+		// Keys (including dotted keys in TOML)
+		} else if self.is_key(self.position)
+			|| (self.grammar.key_separator.is_some() && after.starts_with('.'))
+		{
+			Some(TokenKind::Key)
+		// Macros (the ! is part of the macro's name)
+		} else if self.grammar.macros && after.starts_with('!') && !after.starts_with("!=") {
+			self.position += 1;
+			Some(TokenKind::Attribute)
+		} else if after.trim_start().starts_with('(') {
+			Some(TokenKind::Function)
+		// Capitalized names are types, but names in all caps are usually constants
+		} else if self.grammar.capitalized_types
+			&& word.starts_with(char::is_uppercase)
+			&& word.chars().any(char::is_lowercase)
+		{
+			Some(TokenKind::Type)
+		} else {
+			None
+		};
+
+		if let Some(kind) = kind {
+			self.push(start, self.position, kind);
+		}
+	}
+}
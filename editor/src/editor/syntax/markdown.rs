@@ -0,0 +1,131 @@
+use super::{SyntaxState, Token, TokenKind};
+
+// Tokenize a line of Markdown, starting in the given state
+pub fn tokenize(line: &str, state: SyntaxState) -> (Vec<Token>, SyntaxState) {
+	let trimmed = line.trim_start();
+	// Byte index of the first non-whitespace character
+	let indent = line.len() - trimmed.len();
+	// Highlight the entire line as the given kind
+	let whole_line = |kind| match line.is_empty() {
+		true => Vec::new(),
+		false => vec![Token {
+			start: 0,
+			end: line.len(),
+			kind,
+		}],
+	};
+
+	// Fences start and end blocks of code
+	if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+		let state = match state {
+			SyntaxState::CodeFence => SyntaxState::Normal,
+			_ => SyntaxState::CodeFence,
+		};
+		return (whole_line(TokenKind::String), state);
+	}
+	// Lines within a block of code
+	if state == SyntaxState::CodeFence {
+		return (whole_line(TokenKind::String), state);
+	}
+	// Headings (e.g. ## Usage)
+	let level = trimmed.len() - trimmed.trim_start_matches('#').len();
+	if (1..=6).contains(&level)
+		&& (trimmed[level..].is_empty() || trimmed[level..].starts_with(char::is_whitespace))
+	{
+		return (whole_line(TokenKind::Heading), SyntaxState::Normal);
+	}
+	// Block quotes
+	if trimmed.starts_with('>') {
+		return (whole_line(TokenKind::Comment), SyntaxState::Normal);
+	}
+
+	let mut tokens = Vec::new();
+	// List markers (e.g. -, *, +, or 1.)
+	let marker = list_marker(trimmed);
+	if marker > 0 {
+		tokens.push(Token {
+			start: indent,
+			end: indent + marker,
+			kind: TokenKind::Keyword,
+		});
+	}
+	inline(line, indent + marker, &mut tokens);
+
+	(tokens, SyntaxState::Normal)
+}
+
+// Get the length of the list marker at the start of a line (0 if there isn't one)
+fn list_marker(text: &str) -> usize {
+	// Length of the number of an ordered list
+	let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+	let marker = match digits {
+		0 if text.starts_with(['-', '*', '+']) => 1,
+		0 => return 0,
+		_ if text[digits..].starts_with(['.', ')']) => digits + 1,
+		_ => return 0,
+	};
+	// Markers must be followed by whitespace
+	match text[marker..].starts_with(char::is_whitespace) {
+		true => marker,
+		false => 0,
+	}
+}
+
+// Tokenize the inline elements (code, emphasis, and links) of a line
+fn inline(line: &str, mut position: usize, tokens: &mut Vec<Token>) {
+	while position < line.len() {
+		let rest = &line[position..];
+		// The character before the current position
+		let previous = line[..position].chars().next_back();
+
+		let token = if rest.starts_with('\\') {
+			// Skip the escaped character
+			position += 1;
+			None
+		} else if rest.starts_with('`') {
+			closed(rest, "`", "`").map(|length| (length, TokenKind::String))
+		} else if rest.starts_with("**") || rest.starts_with("__") {
+			closed(rest, &rest[..2], &rest[..2]).map(|length| (length, TokenKind::Emphasis))
+		// Emphasis with underscores can't be in the middle of a word (e.g. snake_case)
+		} else if rest.starts_with('*')
+			|| (rest.starts_with('_') && !previous.is_some_and(char::is_alphanumeric))
+		{
+			closed(rest, &rest[..1], &rest[..1]).map(|length| (length, TokenKind::Emphasis))
+		} else if rest.starts_with('[') || rest.starts_with("![") {
+			link(rest).map(|length| (length, TokenKind::Link))
+		} else if rest.starts_with("<http") {
+			closed(rest, "<", ">").map(|length| (length, TokenKind::Link))
+		} else {
+			None
+		};
+
+		match token {
+			Some((length, kind)) => {
+				tokens.push(Token {
+					start: position,
+					end: position + length,
+					kind,
+				});
+				position += length;
+			}
+			None => position += line[position..].chars().next().map_or(1, char::len_utf8),
+		}
+	}
+}
+
+// Get the length of text starting with `open` and ending with `close` (if it's closed)
+fn closed(text: &str, open: &str, close: &str) -> Option<usize> {
+	let idx = text[open.len()..].find(close)?;
+	// Don't highlight empty pairs (e.g. ** on its own)
+	match idx {
+		0 => None,
+		_ => Some(open.len() + idx + close.len()),
+	}
+}
+
+// Get the length of a link (e.g. [text](url) or ![image](url))
+fn link(text: &str) -> Option<usize> {
+	let label_end = text.find("](")?;
+	let url_length = text[label_end..].find(')')?;
+	Some(label_end + url_length + 1)
+}
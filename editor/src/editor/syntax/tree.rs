@@ -0,0 +1,340 @@
+use std::{iter::once, ops::Range};
+
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{InputEdit, Node, Parser, Point, Query, QueryCursor, Tree};
+
+use super::{Language, Token, TokenKind};
+
+// The compiled in grammar of a language and the node kinds used for structural queries
+struct TreeDefinition {
+	grammar: tree_sitter::Language,
+	// The query used to highlight the syntax tree
+	highlights: &'static str,
+	// Kinds of nodes that are functions
+	functions: &'static [&'static str],
+	// Kinds of nodes that are blocks
+	blocks: &'static [&'static str],
+}
+
+// Get the tree-sitter definition of a language (Markdown isn't parsed with tree-sitter)
+fn definition(language: Language) -> Option<TreeDefinition> {
+	match language {
+		Language::Rust => Some(TreeDefinition {
+			grammar: tree_sitter_rust::LANGUAGE.into(),
+			highlights: tree_sitter_rust::HIGHLIGHTS_QUERY,
+			functions: &["function_item", "closure_expression"],
+			blocks: &[
+				"block",
+				"declaration_list",
+				"field_declaration_list",
+				"enum_variant_list",
+				"match_block",
+			],
+		}),
+		Language::Toml => Some(TreeDefinition {
+			grammar: tree_sitter_toml_ng::LANGUAGE.into(),
+			highlights: tree_sitter_toml_ng::HIGHLIGHTS_QUERY,
+			functions: &[],
+			blocks: &["table", "table_array_element", "inline_table", "array"],
+		}),
+		Language::Markdown => None,
+		Language::Python => Some(TreeDefinition {
+			grammar: tree_sitter_python::LANGUAGE.into(),
+			highlights: tree_sitter_python::HIGHLIGHTS_QUERY,
+			functions: &["function_definition", "lambda"],
+			blocks: &["block"],
+		}),
+		Language::Shell => Some(TreeDefinition {
+			grammar: tree_sitter_bash::LANGUAGE.into(),
+			highlights: tree_sitter_bash::HIGHLIGHT_QUERY,
+			functions: &["function_definition"],
+			blocks: &[
+				"compound_statement",
+				"do_group",
+				"if_statement",
+				"case_statement",
+			],
+		}),
+		Language::Json => Some(TreeDefinition {
+			grammar: tree_sitter_json::LANGUAGE.into(),
+			highlights: tree_sitter_json::HIGHLIGHTS_QUERY,
+			functions: &[],
+			blocks: &["object", "array"],
+		}),
+		// SQL is only tokenized by the generic lexer
+		Language::Sql => None,
+	}
+}
+
+// Get the kind of token for a capture of a highlight query (e.g. @function.macro)
+fn token_kind(capture_name: &str) -> Option<TokenKind> {
+	match capture_name {
+		"function.macro" | "attribute" | "label" => Some(TokenKind::Attribute),
+		"string.special.key" | "property" => Some(TokenKind::Key),
+		"variable.builtin" => Some(TokenKind::Keyword),
+		"number" | "float" => Some(TokenKind::Number),
+		"boolean" => Some(TokenKind::Constant),
+		"constructor" => Some(TokenKind::Type),
+		"escape" => Some(TokenKind::String),
+		// Otherwise, use the first part of the name (e.g. @keyword.return)
+		_ => match capture_name.split('.').next() {
+			Some("keyword") => Some(TokenKind::Keyword),
+			Some("type") => Some(TokenKind::Type),
+			Some("function") => Some(TokenKind::Function),
+			Some("string") => Some(TokenKind::String),
+			Some("constant") => Some(TokenKind::Constant),
+			Some("comment") => Some(TokenKind::Comment),
+			_ => None,
+		},
+	}
+}
+
+// Get the (row, column) point of a byte index in some text
+fn point_of(text: &str, idx: usize) -> Point {
+	let before = &text.as_bytes()[..idx];
+	// The start of the row (after the last newline)
+	let row_start = before
+		.iter()
+		.rposition(|byte| *byte == b'\n')
+		.map_or(0, |newline| newline + 1);
+	Point::new(
+		before.iter().filter(|byte| **byte == b'\n').count(),
+		idx - row_start,
+	)
+}
+
+// Calculate the edit between the old and new text (from their common prefix and suffix)
+fn input_edit(old: &str, new: &str) -> InputEdit {
+	let (old_bytes, new_bytes) = (old.as_bytes(), new.as_bytes());
+	// Length of the text at the start that didn't change
+	let prefix = old_bytes
+		.iter()
+		.zip(new_bytes)
+		.take_while(|(old, new)| old == new)
+		.count();
+	// Length of the text at the end that didn't change (not overlapping the prefix)
+	let suffix = old_bytes[prefix..]
+		.iter()
+		.rev()
+		.zip(new_bytes[prefix..].iter().rev())
+		.take_while(|(old, new)| old == new)
+		.count();
+	let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+
+	InputEdit {
+		start_byte: prefix,
+		old_end_byte: old_end,
+		new_end_byte: new_end,
+		start_position: point_of(old, prefix),
+		old_end_position: point_of(old, old_end),
+		new_end_position: point_of(new, new_end),
+	}
+}
+
+/// A node of the syntax tree found by a structural query (e.g. the function the cursor is in).
+/// Positions are `[byte index within the line, line number]` (like a selection).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxNode {
+	/// The kind of the node in the grammar (e.g. `function_item`)
+	pub kind: &'static str,
+	/// The name of the node (e.g. the name of a function), if it has one
+	pub name: Option<String>,
+	/// The start of the node
+	pub start: [usize; 2],
+	/// The end of the node
+	pub end: [usize; 2],
+}
+
+/* A concrete syntax tree of the text that is loaded into the Blocks. It is kept
+in sync with edits by incrementally reparsing the text that changed. */
+pub struct SyntaxTree {
+	definition: TreeDefinition,
+	parser: Parser,
+	// The query used to highlight the tree
+	query: Query,
+	// The tree (None until the text is parsed)
+	tree: Option<Tree>,
+	// The text that the tree was parsed from
+	source: String,
+	// Byte index of the start of each line in the source
+	line_starts: Vec<usize>,
+	// The line number of the first line of the source
+	starting_line_num: usize,
+}
+
+impl SyntaxTree {
+	// Create a syntax tree for the language (None if there's no grammar for the language)
+	pub fn new(language: Language) -> Option<Self> {
+		let definition = definition(language)?;
+		let mut parser = Parser::new();
+		parser.set_language(&definition.grammar).ok()?;
+		let query = Query::new(&definition.grammar, definition.highlights).ok()?;
+
+		Some(SyntaxTree {
+			definition,
+			parser,
+			query,
+			tree: None,
+			source: String::new(),
+			line_starts: vec![0],
+			starting_line_num: 0,
+		})
+	}
+
+	/* Update the tree to match the given lines of text (starting at the given line number).
+	Returns the ranges of line numbers whose highlighting may have changed. */
+	pub fn update(&mut self, lines: &[&str], starting_line_num: usize) -> Vec<Range<usize>> {
+		let source = lines.join("\n");
+		// Nothing to do if the text hasn't changed
+		if self.tree.is_some()
+			&& source == self.source
+			&& starting_line_num == self.starting_line_num
+		{
+			return Vec::new();
+		}
+
+		// Apply the edit to the old tree so it can be reused when reparsing
+		let edit = input_edit(&self.source, &source);
+		if let Some(tree) = self.tree.as_mut() {
+			tree.edit(&edit);
+		}
+		let tree = self.parser.parse(&source, self.tree.as_ref());
+
+		// The lines that changed (relative to the first line of the source)
+		let changed: Vec<Range<usize>> = match (&self.tree, &tree) {
+			// The edited lines and any lines where the structure of the tree changed
+			(Some(old_tree), Some(new_tree)) => old_tree
+				.changed_ranges(new_tree)
+				.map(|range| range.start_point.row..range.end_point.row + 1)
+				.chain(once(edit.start_position.row..edit.new_end_position.row + 1))
+				.collect(),
+			// Every line changed if there wasn't a tree before
+			_ => once(0..lines.len()).collect(),
+		};
+
+		self.tree = tree;
+		self.line_starts = once(0)
+			.chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+			.collect();
+		self.source = source;
+		self.starting_line_num = starting_line_num;
+
+		changed
+			.into_iter()
+			.map(|range| range.start + starting_line_num..range.end + starting_line_num)
+			.collect()
+	}
+
+	// Highlight the line with the given line number using the highlight query
+	pub fn highlight_line(&self, line_num: usize) -> Vec<Token> {
+		// The byte range of the line in the source
+		let Some(range) = self.line_range(line_num) else {
+			return Vec::new();
+		};
+		let Some(tree) = self.tree.as_ref() else {
+			return Vec::new();
+		};
+
+		// The kind of token of each byte in the line
+		let mut kinds: Vec<Option<TokenKind>> = vec![None; range.len()];
+		// The nodes that have already been captured
+		let mut captured = Vec::new();
+		let mut cursor = QueryCursor::new();
+		cursor.set_byte_range(range.clone());
+		let mut captures = cursor.captures(&self.query, tree.root_node(), self.source.as_bytes());
+		while let Some((query_match, idx)) = captures.next() {
+			let capture = query_match.captures[*idx];
+			// The first pattern that captures a node decides its kind
+			if captured.contains(&capture.node.id()) {
+				continue;
+			}
+			captured.push(capture.node.id());
+
+			let kind = token_kind(self.query.capture_names()[capture.index as usize]);
+			// The part of the node that is on this line
+			let node = capture.node.byte_range();
+			for byte in node.start.max(range.start)..node.end.min(range.end) {
+				kinds[byte - range.start] = kind;
+			}
+		}
+
+		// Join bytes of the same kind into tokens
+		let mut tokens: Vec<Token> = Vec::new();
+		for (idx, kind) in kinds.into_iter().enumerate() {
+			let Some(kind) = kind else {
+				continue;
+			};
+			match tokens.last_mut() {
+				Some(token) if token.end == idx && token.kind == kind => token.end += 1,
+				_ => tokens.push(Token {
+					start: idx,
+					end: idx + 1,
+					kind,
+				}),
+			}
+		}
+
+		tokens
+	}
+
+	// Get the function that contains the given position (byte index within the given line)
+	pub fn current_function(&self, line_num: usize, text_position: usize) -> Option<SyntaxNode> {
+		self.enclosing_node(line_num, text_position, self.definition.functions)
+	}
+
+	// Get the innermost block that contains the given position (byte index within the given line)
+	pub fn enclosing_block(&self, line_num: usize, text_position: usize) -> Option<SyntaxNode> {
+		self.enclosing_node(line_num, text_position, self.definition.blocks)
+	}
+
+	// Get the innermost node of one of the given kinds that contains the given position
+	fn enclosing_node(
+		&self,
+		line_num: usize,
+		text_position: usize,
+		kinds: &[&str],
+	) -> Option<SyntaxNode> {
+		let point = Point::new(line_num.checked_sub(self.starting_line_num)?, text_position);
+		let mut node = self
+			.tree
+			.as_ref()?
+			.root_node()
+			.descendant_for_point_range(point, point);
+		// Move up the tree until a node of the right kind is found
+		while let Some(current) = node {
+			if kinds.contains(&current.kind()) {
+				return Some(self.syntax_node(current));
+			}
+			node = current.parent();
+		}
+
+		None
+	}
+
+	// Convert a node of the tree to a SyntaxNode (with line numbers of the entire file)
+	fn syntax_node(&self, node: Node) -> SyntaxNode {
+		let (start, end) = (node.start_position(), node.end_position());
+		SyntaxNode {
+			kind: node.kind(),
+			name: node
+				.child_by_field_name("name")
+				.and_then(|name| name.utf8_text(self.source.as_bytes()).ok())
+				.map(String::from),
+			start: [start.column, start.row + self.starting_line_num],
+			end: [end.column, end.row + self.starting_line_num],
+		}
+	}
+
+	// Get the byte range of the line with the given line number within the source
+	fn line_range(&self, line_num: usize) -> Option<Range<usize>> {
+		let idx = line_num.checked_sub(self.starting_line_num)?;
+		let start = *self.line_starts.get(idx)?;
+		// Lines end before the newline at the start of the next line
+		let end = match self.line_starts.get(idx + 1) {
+			Some(next) => next - 1,
+			None => self.source.len(),
+		};
+
+		Some(start..end)
+	}
+}
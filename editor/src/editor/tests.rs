@@ -8,6 +8,30 @@ said highlight. */
 mod selection_tests;
 /* Tests for the key_functions module. */
 mod key_functions_tests;
+/* Tests for how text is displayed in the widget
+(e.g. soft wrapping long lines). */
+mod display_tests;
+/* Tests for detecting the language of a file
+and syntax highlighting its text. */
+mod syntax_tests;
+/* Tests for clicking, dragging, and scrolling
+with the mouse. */
+mod mouse_tests;
+/* Tests for editing with multiple
+cursors. */
+mod multi_cursor_tests;
+/* Tests for selecting and editing rectangular
+blocks of text. */
+mod block_selection_tests;
+/* Tests for moving, duplicating, joining,
+and deleting lines. */
+mod line_keys_tests;
+/* Tests for toggling line and block
+comments. */
+mod comment_keys_tests;
+/* Tests for finding and jumping to
+matching brackets. */
+mod bracket_match_tests;
 
 /*
 ========================================
@@ -54,6 +78,9 @@ const SMALL_FILE: &str = "../editor/test_files/small_text.txt";
 // Text file used to test basic highlighting functionality
 const HIGHLIGHT_FILE: &str = "../editor/test_files/highlight.txt";
 
+// Python file that is indented with two spaces
+const SPACES_FILE: &str = "../editor/test_files/spaces.py";
+
 /*
 =============================================
 			GENOME FILE CONSTANTS
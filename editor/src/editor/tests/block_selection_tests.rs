@@ -0,0 +1,98 @@
+/*
+=============================================
+			BLOCK SELECTION TESTS
+=============================================
+*/
+
+use super::*;
+use key_functions::{
+	block_selection::*, editing_keys::char_key, multi_cursor, navigation_keys::jump_to, undo_redo,
+};
+use unredo_stack::stack_choice::StackChoice;
+
+// Select the block from the 2nd column of the 3rd line to the 5th column of the 5th line
+fn select_test_block(editor: &mut EditorSpace) {
+	jump_to(editor, 2, 1);
+	for _i in 0..2 {
+		block_down(editor);
+	}
+	for _i in 0..3 {
+		block_right(editor);
+	}
+}
+
+// Test selecting a block by display column (and copying it)
+#[test]
+fn select_block_by_column() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	select_test_block(&mut editor);
+
+	// The cursor is on the moving corner of the block
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 4);
+	assert_eq!(editor.cursors.len(), 2);
+	// "oid" is selected on the 3rd line
+	assert_eq!(editor.cursors[0].selection.start, [1, 2]);
+	assert_eq!(editor.cursors[0].selection.end, [4, 2]);
+	// The tab at the start of the 4th line is partially within the block
+	assert_eq!(editor.cursors[1].selection.start, [0, 3]);
+	assert_eq!(editor.cursors[1].selection.end, [1, 3]);
+	// The 5th line is shorter than the block
+	assert!(editor.selection.is_empty);
+
+	// Each row of the block is copied as a line
+	assert_eq!(copy_text(&mut editor), "oid\n\t\n");
+}
+
+// Test typing (and deleting) on every row of a block
+#[test]
+fn edit_block_rows() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	select_test_block(&mut editor);
+
+	// Typing replaces the block on every row
+	multi_cursor::edit(&mut editor, false, |editor| char_key(editor, 'X'));
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "vX test_func() {");
+	assert!(content[3].starts_with("Xprintf("));
+	assert_eq!(content[4], "}X");
+
+	// Undo and delete the block instead
+	undo_redo(&mut editor, StackChoice::Undo);
+	select_test_block(&mut editor);
+	assert!(is_selecting(&editor));
+	delete(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "v test_func() {");
+	assert!(content[3].starts_with("printf("));
+	assert_eq!(content[4], "}");
+	assert_eq!(editor.file_length, 13);
+}
+
+// Test pasting a block below the cursor (padding short lines and adding lines to the file)
+#[test]
+fn paste_as_block() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Paste the block over the first two lines
+	jump_to(&mut editor, 0, 2);
+	paste_block(&mut editor, "ab\ncd");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "#iabnclude<stdio.h>");
+	assert_eq!(content[1], "  cd");
+	// The entire paste is undone at once
+	undo_redo(&mut editor, StackChoice::Undo);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "#include<stdio.h>");
+	assert_eq!(content[1], "");
+
+	// Paste a block that goes past the end of the file
+	jump_to(&mut editor, 12, 0);
+	paste_block(&mut editor, "a\nb");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[12], "a");
+	assert_eq!(content[13], "b");
+	assert_eq!(editor.file_length, 14);
+}
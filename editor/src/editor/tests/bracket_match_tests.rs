@@ -0,0 +1,107 @@
+/*
+=========================================
+			BRACKET MATCH TESTS
+=========================================
+*/
+
+use super::*;
+use key_functions::{bracket_match::*, navigation_keys::jump_to};
+
+// Test jumping between the brackets of a function
+#[test]
+fn jump_between_brackets() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Jump from the opening brace of test_func to its closing brace (and back)
+	jump_to(&mut editor, 2, 17);
+	jump_to_match(&mut editor);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 4);
+	assert_eq!(editor.text_position, 0);
+	jump_to_match(&mut editor);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 2);
+	assert_eq!(editor.text_position, 17);
+
+	// The bracket just before the cursor is matched too
+	jump_to(&mut editor, 8, 11);
+	jump_to_match(&mut editor);
+	assert_eq!(editor.text_position, 10);
+
+	// Nothing happens without a bracket at the cursor
+	jump_to(&mut editor, 0, 2);
+	jump_to_match(&mut editor);
+	assert_eq!(editor.text_position, 2);
+	assert_eq!(
+		editor.status_message.as_ref().unwrap().text,
+		"No bracket at the cursor"
+	);
+}
+
+// Test that brackets in strings and comments are skipped
+#[test]
+fn skip_strings_and_comments() {
+	// Make an editor for the SMALL_FILE (as a Rust file)
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.language = Some(Language::Rust);
+	editor
+		.blocks
+		.as_mut()
+		.unwrap()
+		.update_some_line(String::from("(\")\" /* ) */ )"), 12)
+		.unwrap();
+
+	// The closing parenthesis after the string and the comment is the match
+	jump_to(&mut editor, 12, 0);
+	jump_to_match(&mut editor);
+	assert_eq!(editor.text_position, 13);
+	jump_to_match(&mut editor);
+	assert_eq!(editor.text_position, 0);
+
+	// Without syntax highlighting, the parenthesis in the string is the match
+	editor.language = None;
+	editor.blocks.as_mut().unwrap().blocks_list[0].highlights = vec![None; 13];
+	jump_to_match(&mut editor);
+	assert_eq!(editor.text_position, 2);
+}
+
+// Test jumping to a matching bracket in a block that isn't loaded
+#[test]
+fn jump_across_blocks() {
+	// Write a function with a long body to a test file
+	let filename = "../editor/test_files/bracket_match-debug-test.rs";
+	let body = vec!["\tlet x = 1;"; 5000].join("\n");
+	std::fs::write(filename, format!("fn main() {{\n{}\n}}\n", body)).unwrap();
+	// Make an editor for the test file
+	let mut editor = construct_editor(filename);
+	std::fs::remove_file(filename).unwrap();
+
+	// Jump from the opening brace to the closing brace (loading in the blocks after it)
+	jump_to(&mut editor, 0, 10);
+	jump_to_match(&mut editor);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 5001);
+	assert_eq!(editor.text_position, 0);
+	// The first block was unloaded on the way down
+	assert!(editor.blocks.as_ref().unwrap().head_block > 0);
+
+	// Jump back up to the opening brace (loading in the blocks before it)
+	jump_to_match(&mut editor);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 0);
+	assert_eq!(editor.text_position, 10);
+}
+
+// Test that Ctrl+5 only jumps to the matching bracket when the terminal sends Ctrl+] as Ctrl+5
+#[test]
+fn legacy_bracket_key() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	jump_to(&mut editor, 2, 17);
+
+	// A terminal with keyboard enhancements sends a real Ctrl+5
+	input_handlers::control_modifier(&mut editor, KeyCode::Char('5'), &mut false);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 2);
+
+	// Other terminals send Ctrl+] as Ctrl+5
+	editor.set_legacy_keys(true);
+	input_handlers::control_modifier(&mut editor, KeyCode::Char('5'), &mut false);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 4);
+}
@@ -0,0 +1,142 @@
+/*
+=========================================
+			COMMENT KEYS TESTS
+=========================================
+*/
+
+use super::*;
+use key_functions::{
+	comment_keys::*, highlight_keys::select_range, navigation_keys::jump_to, undo_redo,
+};
+use unredo_stack::stack_choice::StackChoice;
+
+// Test commenting and uncommenting the selected lines
+#[test]
+fn toggle_selected_line_comments() {
+	// Make an editor for the SMALL_FILE (as a Rust file)
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.language = Some(Language::Rust);
+
+	// The comments line up at the smallest indentation of the lines
+	select_range(&mut editor, [0, 6], [3, 8]);
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[6], "// int main() {");
+	assert!(content[7].starts_with("// \tprintf"));
+	assert_eq!(content[8], "// \ttest_func();");
+	// The selection moves with its text
+	assert_eq!(editor.selection.start, [0, 6]);
+	assert_eq!(editor.selection.end, [6, 8]);
+
+	// Commenting is a single undo state
+	undo_redo(&mut editor, StackChoice::Undo);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(
+		content[6..9],
+		["int main() {", content[7].as_str(), "\ttest_func();"]
+	);
+	assert!(content[7].starts_with("\tprintf"));
+
+	// Lines with a blank line between them (which isn't commented)
+	editor.selection.is_empty = true;
+	select_range(&mut editor, [0, 8], [4, 10]);
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[8..11], ["\t// test_func();", "", "\t// return 0;"]);
+	// Every line is commented, so they're uncommented
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[8..11], ["\ttest_func();", "", "\treturn 0;"]);
+}
+
+// Test toggling line comments with the tokens of different languages
+#[test]
+fn language_line_comments() {
+	// Make an editor for the SPACES_FILE (a Python file)
+	let mut editor = construct_editor(SPACES_FILE);
+
+	// Comment the cursor's line
+	jump_to(&mut editor, 1, 4);
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "  # if True:");
+	assert_eq!(editor.text_position, 6);
+	// A line that's already commented is uncommented
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "  if True:");
+
+	// SQL line comments start with --
+	editor.language = Some(Language::Sql);
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "  -- if True:");
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "  if True:");
+
+	// JSON doesn't have comments
+	editor.language = Some(Language::Json);
+	toggle_line_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "  if True:");
+	assert_eq!(
+		editor.status_message.as_ref().unwrap().text,
+		"JSON doesn't have line comments"
+	);
+}
+
+// Test that Ctrl+7 only toggles line comments when the terminal sends Ctrl+/ as Ctrl+7
+#[test]
+fn legacy_comment_key() {
+	// Make an editor for the SPACES_FILE (a Python file)
+	let mut editor = construct_editor(SPACES_FILE);
+	jump_to(&mut editor, 1, 0);
+
+	// A terminal with keyboard enhancements sends a real Ctrl+7
+	input_handlers::control_modifier(&mut editor, KeyCode::Char('7'), &mut false);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "  if True:");
+
+	// Other terminals send Ctrl+/ as Ctrl+7
+	editor.set_legacy_keys(true);
+	input_handlers::control_modifier(&mut editor, KeyCode::Char('7'), &mut false);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "  # if True:");
+}
+
+// Test wrapping the selection (and the cursor's line) in a block comment
+#[test]
+fn toggle_block_comments() {
+	// Make an editor for the SMALL_FILE (as a Rust file)
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.language = Some(Language::Rust);
+
+	// Comment the selected text
+	select_range(&mut editor, [1, 8], [12, 8]);
+	toggle_block_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[8], "\t/* test_func() */;");
+	assert_eq!(editor.selection.start, [1, 8]);
+	assert_eq!(editor.selection.end, [18, 8]);
+	// The selection is already commented, so it's uncommented
+	toggle_block_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[8], "\ttest_func();");
+	assert_eq!(editor.selection.end, [12, 8]);
+
+	// Comment a selection over multiple lines
+	editor.selection.is_empty = true;
+	select_range(&mut editor, [0, 2], [1, 4]);
+	toggle_block_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "/* void test_func() {");
+	assert_eq!(content[4], "} */");
+
+	// Without a selection, the text of the cursor's line is commented
+	editor.selection.is_empty = true;
+	jump_to(&mut editor, 10, 0);
+	toggle_block_comment(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[10], "\t/* return 0; */");
+}
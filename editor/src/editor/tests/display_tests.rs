@@ -0,0 +1,698 @@
+/*
+=====================================
+			DISPLAY TESTS
+=====================================
+*/
+
+use super::*;
+use config::config::Whitespace;
+use key_functions::{highlight_keys::*, navigation_keys::*, *};
+use ratatui::{
+	backend::TestBackend,
+	buffer::Buffer,
+	layout::{Constraint, Direction, Layout},
+	style::Modifier,
+	Terminal,
+};
+
+// Render the editor (with a line numbers widget of width 9) and return the rendered rows
+fn render_editor(editor: &mut EditorSpace, width: u16, height: u16) -> Vec<String> {
+	buffer_rows(&render_buffer(editor, width, height))
+}
+
+// Render the editor (with a line numbers widget of width 9) and return the rendered buffer
+fn render_buffer(editor: &mut EditorSpace, width: u16, height: u16) -> Buffer {
+	// Create a terminal that renders to a buffer
+	let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+	terminal
+		.draw(|frame| {
+			// Layout for the line numbers and editor widgets
+			let layout = Layout::new(
+				Direction::Horizontal,
+				[Constraint::Length(9), Constraint::Length(width - 9)],
+			)
+			.split(frame.size());
+			editor.render_ui(frame, layout);
+		})
+		.unwrap();
+
+	terminal.backend().buffer().clone()
+}
+
+/* Split rendered rows into the (line number, text) shown in the two widgets.
+Borders and trailing whitespace are removed. */
+fn split_widgets(rows: &[String]) -> Vec<(String, String)> {
+	rows.iter()
+		.map(|row| {
+			let chars: Vec<char> = row.chars().collect();
+			// The line numbers widget has a left border and the editor has borders on both sides
+			let line_num: String = chars[1..9].iter().collect();
+			let text: String = chars[10..chars.len() - 1].iter().collect();
+			(line_num.trim().to_string(), text.trim_end().to_string())
+		})
+		.collect()
+}
+
+// Get the rows of a rendered buffer as strings
+fn buffer_rows(buffer: &Buffer) -> Vec<String> {
+	(0..buffer.area.height)
+		.map(|y| {
+			(0..buffer.area.width)
+				.map(|x| buffer.get(x, y).symbol())
+				.collect::<String>()
+		})
+		.collect()
+}
+
+/*
+=========================================
+			SOFT WRAP TESTS
+=========================================
+*/
+
+// Construct an editor for the GENOME_FILE with soft wrapping in a 40 column widget
+fn construct_wrapped_editor() -> EditorSpace {
+	// Create a config with soft wrapping
+	let config = Config {
+		soft_wrap: true,
+		..Default::default()
+	};
+	// Create an EditorSpace over the GENOME_FILE
+	let mut editor = EditorSpace::new(String::from(GENOME_FILE), config);
+	// Initialize the editor with 40 columns for text (plus 2 for borders)
+	let _ = editor.init_editor((0, 0), 42, 50);
+
+	editor
+}
+
+// Test that lines are wrapped at word boundaries (and within words that are too long)
+#[test]
+fn wrap_points_word_boundaries() {
+	// Make an editor for the GENOME_FILE
+	let editor = construct_wrapped_editor();
+	let blocks = editor.blocks.as_ref().unwrap();
+
+	// The first line wraps at word boundaries
+	let line = blocks.get_some_line(0).unwrap();
+	let rows: Vec<&str> = split_rows(&line, &editor.get_wrap_points(&line));
+	assert_eq!(
+		rows,
+		vec![
+			">NM_000014.6 Homo sapiens ",
+			"alpha-2-macroglobulin (A2M), transcript ",
+			"variant 1, mRNA"
+		]
+	);
+
+	// The second line has no whitespace, so it wraps at the width of the widget
+	let line = blocks.get_some_line(1).unwrap();
+	let rows: Vec<&str> = split_rows(&line, &editor.get_wrap_points(&line));
+	assert_eq!(rows, vec![&line[..40], &line[40..]]);
+
+	// Tabs are wrapped using their full width
+	let points = soft_wrap::wrap_points("\tabc\tdef", 6, 4);
+	assert_eq!(points, vec![(0, 0), (1, 4), (4, 7), (5, 11)]);
+}
+
+// Test that up and down move by visual row while keeping the column
+#[test]
+fn wrapped_up_down() {
+	// Make an editor for the GENOME_FILE
+	let mut editor = construct_wrapped_editor();
+
+	// Move right 3 characters on the first row
+	for _i in 0..3 {
+		right_arrow(&mut editor, true);
+	}
+
+	// Move down through the rows of the first two lines
+	let expected = [(0, 29), (0, 69), (1, 3), (1, 43)];
+	for (line_num, text_position) in expected {
+		down_arrow(&mut editor);
+		assert_eq!(editor.get_line_num(editor.cursor_position[1]), line_num);
+		assert_eq!(editor.text_position, text_position);
+	}
+
+	// Check the position of the cursor on the screen
+	assert_eq!(editor.get_screen_cursor(), (3, 4));
+
+	// Move back up through the rows
+	let expected = [(1, 3), (0, 69), (0, 29), (0, 3)];
+	for (line_num, text_position) in expected {
+		up_arrow(&mut editor);
+		assert_eq!(editor.get_line_num(editor.cursor_position[1]), line_num);
+		assert_eq!(editor.text_position, text_position);
+	}
+}
+
+// Test that line numbers are only shown on the first row of each line
+#[test]
+fn wrapped_line_numbers() {
+	// Make an editor for the GENOME_FILE
+	let editor = construct_wrapped_editor();
+
+	// The line numbers of the first three lines
+	let line_nums: Vec<String> = editor
+		.get_wrapped_line_numbers()
+		.iter()
+		.take(7)
+		.map(|line| line.to_string())
+		.collect();
+	assert_eq!(line_nums, vec!["1", "", "", "2", "", "3", ""]);
+}
+
+// Test rendering soft wrapped lines in the widget
+#[test]
+fn render_wrapped_lines() {
+	// Create an editor for the GENOME_FILE with soft wrapping
+	let config = Config {
+		soft_wrap: true,
+		..Default::default()
+	};
+	let mut editor = EditorSpace::new(String::from(GENOME_FILE), config);
+
+	// Render the editor with 40 columns for text
+	let rows = split_widgets(&render_editor(&mut editor, 51, 10));
+	// The line numbers and text of the first four rows
+	let rows: Vec<(&str, &str)> = rows[1..5]
+		.iter()
+		.map(|(num, text)| (num.as_str(), text.as_str()))
+		.collect();
+	assert_eq!(
+		rows,
+		[
+			("1", ">NM_000014.6 Homo sapiens"),
+			("", "alpha-2-macroglobulin (A2M), transcript"),
+			("", "variant 1, mRNA"),
+			("2", "GGGACCAGATGGATTGTAGGGAGTAGGGTACAATACAGTC"),
+		]
+	);
+}
+
+// Test that toggling soft wrapping off scrolls horizontally to the cursor
+#[test]
+fn toggle_soft_wrap_cursor() {
+	// Make an editor for the GENOME_FILE
+	let mut editor = construct_wrapped_editor();
+
+	// Move to the end of the first line
+	end_key(&mut editor, true);
+	assert_eq!(editor.cursor_position[0], 81);
+
+	// Turn off soft wrapping (the cursor stays at the end of the line)
+	toggle_soft_wrap(&mut editor);
+	assert_eq!(editor.cursor_position[0], 81);
+
+	// Render the editor with 40 columns for text
+	render_editor(&mut editor, 51, 10);
+	assert_eq!(editor.horizontal_offset, 43);
+	assert_eq!(editor.get_screen_cursor(), (38, 0));
+}
+
+/*
+=============================================
+			HORIZONTAL SCROLL TESTS
+=============================================
+*/
+
+// Test clipping lines to the visible columns (with indicators for clipped text)
+#[test]
+fn clip_line_indicators() {
+	// Clip a line and get its text
+	let clip = |text: &'static str, offset, width| {
+		horizontal_scroll::clip_line(Line::from(text), offset, width).to_string()
+	};
+
+	// Text to the right of the widget
+	assert_eq!(clip("abcdefghij", 0, 5), "abcd»");
+	// Text on both sides of the widget
+	assert_eq!(clip("abcdefghij", 3, 5), "«efg»");
+	// Text to the left of the widget
+	assert_eq!(clip("abcdefghij", 6, 5), "«hij");
+	// The whole line is to the left of the widget
+	assert_eq!(clip("abc", 5, 5), "«");
+	// An empty line has nothing to indicate
+	assert_eq!(clip("", 5, 5), "");
+	// Partially visible wide graphemes are replaced
+	assert_eq!(clip("a\u{4e2d}bcd", 2, 3), "«b»");
+}
+
+// Test that the horizontal offset follows the cursor past either edge of the widget
+#[test]
+fn horizontal_scroll_follows_cursor() {
+	// Make an editor for the GENOME_FILE (initialized with 40 columns for text)
+	let mut editor = EditorSpace::new(String::from(GENOME_FILE), Config::default());
+	render_editor(&mut editor, 51, 10);
+
+	// Move to the end of the first line
+	end_key(&mut editor, true);
+	// Render the editor with 40 columns for text
+	let rows = split_widgets(&render_editor(&mut editor, 51, 10));
+	assert_eq!(editor.horizontal_offset, 43);
+	assert_eq!(editor.get_screen_cursor(), (38, 0));
+
+	// The visible text of the first two lines
+	let blocks = editor.blocks.as_ref().unwrap();
+	let first_line = blocks.get_some_line(0).unwrap();
+	let second_line = blocks.get_some_line(1).unwrap();
+	assert_eq!(rows[1].1, String::from("«") + &first_line[44..]);
+	assert_eq!(rows[2].1, String::from("«") + &second_line[44..]);
+
+	// Move back to the start of the line
+	home_key(&mut editor, true);
+	let rows = split_widgets(&render_editor(&mut editor, 51, 10));
+	assert_eq!(editor.horizontal_offset, 0);
+	assert_eq!(rows[1].1, String::from(&first_line[..39]) + "»");
+}
+
+// Test that the selection is highlighted in the correct columns when scrolled
+#[test]
+fn highlight_scrolled_selection() {
+	// Make an editor for the GENOME_FILE (initialized with 40 columns for text)
+	let mut editor = EditorSpace::new(String::from(GENOME_FILE), Config::default());
+	render_editor(&mut editor, 51, 10);
+
+	// Select the last 3 characters of the first line
+	end_key(&mut editor, true);
+	for _i in 0..3 {
+		highlight_left(&mut editor);
+	}
+
+	// Render the editor with 40 columns for text
+	let buffer = render_buffer(&mut editor, 51, 10);
+	assert_eq!(editor.horizontal_offset, 40);
+	// The background color of the cell showing the given text column (on the first line)
+	let background = |column: usize| buffer.get((column - 40 + 10) as u16, 1).bg;
+	let highlight = editor.config.theme.selection_highlight;
+	assert_ne!(background(77), highlight);
+	// The last visible column is covered by the right indicator (but still highlighted)
+	for column in 78..80 {
+		assert_eq!(background(column), highlight);
+	}
+}
+
+// Split a line into its visual rows at the given wrap points
+fn split_rows<'a>(line: &'a str, points: &[(usize, usize)]) -> Vec<&'a str> {
+	points
+		.iter()
+		.enumerate()
+		.map(|(idx, (start, _))| match points.get(idx + 1) {
+			Some((end, _)) => &line[*start..*end],
+			None => &line[*start..],
+		})
+		.collect()
+}
+
+/*
+=========================================
+			STATUS BAR TESTS
+=========================================
+*/
+
+// Render the status bar of the editor and return its text (without the borders)
+fn render_status_bar(editor: &EditorSpace, width: u16) -> String {
+	let mut terminal = Terminal::new(TestBackend::new(width, 1)).unwrap();
+	terminal
+		.draw(|frame| editor.render_status_bar(frame, frame.size()))
+		.unwrap();
+	let row = buffer_rows(terminal.backend().buffer()).remove(0);
+	// Remove the borders on either side
+	let chars: Vec<char> = row.chars().collect();
+	chars[1..chars.len() - 1].iter().collect()
+}
+
+// Test the file and cursor information shown in the status bar
+#[test]
+fn status_bar_information() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	// Move to the 3rd line, 4th column
+	down_arrow(&mut editor);
+	down_arrow(&mut editor);
+	for _i in 0..3 {
+		right_arrow(&mut editor, true);
+	}
+
+	let status = render_status_bar(&editor, 120);
+	assert!(status.starts_with(&format!(" {}  ", SMALL_FILE)));
+	assert!(status.ends_with(&format!(
+		"Ln 3, Col 4 | {} lines | UTF-8 | LF | Tabs: 4 | Plain Text ",
+		editor.file_length
+	)));
+
+	// Modifying the text marks the file as modified
+	editing_keys::char_key(&mut editor, 'a');
+	let status = render_status_bar(&editor, 120);
+	assert!(status.starts_with(&format!(" {} [+]  ", SMALL_FILE)));
+}
+
+// Test that transient messages are shown (and cut off when there isn't room)
+#[test]
+fn status_bar_messages() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Toggling soft wrapping shows a message
+	toggle_soft_wrap(&mut editor);
+	let status = render_status_bar(&editor, 120);
+	assert!(status.contains("  Soft wrap on "));
+
+	// The left side is cut off when there isn't enough room
+	let status = render_status_bar(&editor, 80);
+	assert_eq!(status.chars().count(), 78);
+	assert!(status.ends_with("| Plain Text "));
+	assert!(!status.contains("Soft wrap"));
+
+	// Messages expire
+	editor.status_message.as_mut().unwrap().time -= Duration::from_secs(60);
+	let status = render_status_bar(&editor, 120);
+	assert!(!status.contains("Soft wrap"));
+}
+
+// Test detecting the encoding and line ending of a file
+#[test]
+fn status_bar_detection() {
+	assert_eq!(status_bar::detect_encoding(b"plain text"), "UTF-8");
+	assert_eq!(
+		status_bar::detect_encoding(b"\xEF\xBB\xBFtext"),
+		"UTF-8 BOM"
+	);
+	assert_eq!(
+		status_bar::detect_encoding(b"bad \xFF byte"),
+		"UTF-8 (lossy)"
+	);
+	assert_eq!(status_bar::detect_line_ending("a\nb\nc"), "LF");
+	assert_eq!(status_bar::detect_line_ending("a\r\nb\r\nc\n"), "CRLF");
+}
+
+/*
+===========================================
+			LINE NUMBER TESTS
+===========================================
+*/
+
+// Test that the width of the line numbers widget depends on the length of the file
+#[test]
+fn gutter_width() {
+	// The SMALL_FILE has 2 digit line numbers and the GENOME_FILE has 3
+	assert_eq!(construct_editor(SMALL_FILE).gutter_width(), 4);
+	assert_eq!(construct_editor(GENOME_FILE).gutter_width(), 5);
+
+	// Hidden line numbers don't take up any space
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.config.line_numbers = LineNumbers::Hidden;
+	assert_eq!(editor.gutter_width(), 0);
+}
+
+// Test showing line numbers relative to the cursor's line
+#[test]
+fn relative_line_numbers() {
+	// Make an editor for the SMALL_FILE with relative line numbers
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.config.line_numbers = LineNumbers::Relative;
+	// Move to the 3rd line
+	down_arrow(&mut editor);
+	down_arrow(&mut editor);
+
+	let rows = split_widgets(&render_editor(&mut editor, 40, 7));
+	let line_nums: Vec<&str> = rows[1..6].iter().map(|(num, _)| num.as_str()).collect();
+	assert_eq!(line_nums, vec!["2", "1", "0", "1", "2"]);
+
+	// Hybrid line numbers show the cursor's actual line number
+	editor.config.line_numbers = LineNumbers::Hybrid;
+	let rows = split_widgets(&render_editor(&mut editor, 40, 7));
+	let line_nums: Vec<&str> = rows[1..6].iter().map(|(num, _)| num.as_str()).collect();
+	assert_eq!(line_nums, vec!["2", "1", "3", "1", "2"]);
+}
+
+// Test that the editor widget keeps up with the width of the line numbers widget
+#[test]
+fn gutter_width_layout() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = EditorSpace::new(String::from(SMALL_FILE), Config::default());
+	render_editor(&mut editor, 40, 10);
+	// The editor widget was 31 columns wide (minus 2 for borders) on the first render
+	assert_eq!(editor.width, 29);
+
+	// Render with a smaller line numbers widget
+	let mut terminal = Terminal::new(TestBackend::new(40, 10)).unwrap();
+	terminal
+		.draw(|frame| {
+			let width = editor.gutter_width();
+			let layout = Layout::new(
+				Direction::Horizontal,
+				[Constraint::Length(width), Constraint::Length(40 - width)],
+			)
+			.split(frame.size());
+			editor.render_ui(frame, layout);
+		})
+		.unwrap();
+	assert_eq!(editor.width, 34);
+	assert_eq!(editor.widget_horz_bounds, (4, 40));
+}
+
+/*
+===========================================
+			WHITESPACE TESTS
+===========================================
+*/
+
+// Get the text displayed for a line with the given whitespace settings
+fn display_whitespace(line: &str, whitespace: Whitespace, indent_guides: bool) -> String {
+	let config = Config {
+		whitespace,
+		indent_guides,
+		..Default::default()
+	};
+	let renderer = WhitespaceRenderer::new(line, &config);
+	renderer
+		.spans(0, line, Style::default())
+		.iter()
+		.map(|span| span.content.to_string())
+		.collect()
+}
+
+// Test the markers of each kind of whitespace
+#[test]
+fn visible_whitespace() {
+	let line = "\tif a\u{a0}b  ";
+	// Only indent guides are shown by default
+	assert_eq!(
+		display_whitespace(line, Whitespace::Hidden, true),
+		"\u{2502}   if a\u{a0}b  "
+	);
+	// Only the trailing whitespace and the non-breaking space are marked
+	assert_eq!(
+		display_whitespace(line, Whitespace::Trailing, true),
+		"\u{2502}   if a\u{237d}b\u{b7}\u{b7}"
+	);
+	// All whitespace is marked
+	assert_eq!(
+		display_whitespace(line, Whitespace::All, true),
+		"\u{2192}   if\u{b7}a\u{237d}b\u{b7}\u{b7}"
+	);
+	// Tabs are blank without indent guides
+	assert_eq!(
+		display_whitespace(line, Whitespace::Hidden, false),
+		"    if a\u{a0}b  "
+	);
+}
+
+// Test that indent guides are shown at each level of indentation
+#[test]
+fn indent_guides() {
+	assert_eq!(
+		display_whitespace("          x  y", Whitespace::Hidden, true),
+		"\u{2502}   \u{2502}   \u{2502} x  y"
+	);
+	assert_eq!(
+		display_whitespace("\t  \tx", Whitespace::Hidden, true),
+		"\u{2502}   \u{2502} \u{2502}   x"
+	);
+}
+
+// Test that the grapheme after a zero-width character is marked
+#[test]
+fn zero_width_markers() {
+	let config = Config {
+		whitespace: Whitespace::Trailing,
+		..Default::default()
+	};
+	let line = "a\u{200b}bc";
+	let renderer = WhitespaceRenderer::new(line, &config);
+	// The 'b' (after the zero-width space) is underlined
+	let (text, style) = renderer.display(4, "b");
+	assert_eq!(text, "b");
+	assert!(style.add_modifier.contains(Modifier::UNDERLINED));
+	// The other graphemes aren't
+	let (_, style) = renderer.display(5, "c");
+	assert_eq!(style, Style::default());
+}
+
+// Test that trailing whitespace is highlighted when rendered
+#[test]
+fn render_trailing_whitespace() {
+	// Make an editor for the SMALL_FILE with visible trailing whitespace
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.config.whitespace = Whitespace::Trailing;
+	editing_keys::char_key(&mut editor, ' ');
+	end_key(&mut editor, true);
+	editing_keys::char_key(&mut editor, ' ');
+
+	let buffer = render_buffer(&mut editor, 60, 5);
+	// The first line (after the borders and line numbers) ends with a marked space
+	let rows = split_widgets(&buffer_rows(&buffer));
+	assert_eq!(rows[1].1, "\u{2502}#include<stdio.h>\u{b7}");
+	// Only the trailing space has the trailing whitespace background
+	let bg = editor.config.theme.trailing_whitespace_bg;
+	assert_ne!(buffer.get(10, 1).style().bg, Some(bg));
+	assert_eq!(buffer.get(28, 1).style().bg, Some(bg));
+}
+
+// Test that the bracket at the cursor and its match are highlighted when rendered
+#[test]
+fn render_matching_brackets() {
+	// Make an editor for the SMALL_FILE with the cursor on the parentheses of test_func
+	let mut editor = construct_editor(SMALL_FILE);
+	jump_to(&mut editor, 2, 14);
+
+	let buffer = render_buffer(&mut editor, 60, 6);
+	// Only the parentheses have the bracket background (the line starts after the border)
+	let bg = editor.config.theme.bracket_match_bg;
+	assert_eq!(buffer.get(24, 3).style().bg, Some(bg));
+	assert_eq!(buffer.get(25, 3).style().bg, Some(bg));
+	assert_ne!(buffer.get(23, 3).style().bg, Some(bg));
+	assert_ne!(buffer.get(27, 3).style().bg, Some(bg));
+
+	// The opening brace of the function matches its closing brace on another line
+	jump_to(&mut editor, 2, 17);
+	let buffer = render_buffer(&mut editor, 60, 8);
+	assert_eq!(buffer.get(27, 3).style().bg, Some(bg));
+	assert_eq!(buffer.get(10, 5).style().bg, Some(bg));
+}
+
+/*
+=======================================
+			RULER TESTS
+=======================================
+*/
+
+// Test finding the text past the line length limit (with tabs and wide graphemes)
+#[test]
+fn line_limit_index() {
+	assert_eq!(rulers::limit_index("abcdef", 4, 4), Some(4));
+	assert_eq!(rulers::limit_index("abcd", 4, 4), None);
+	// Tabs are tab_width columns wide
+	assert_eq!(rulers::limit_index("\tabc", 5, 4), Some(2));
+	// Wide graphemes that cross the limit are past it
+	assert_eq!(rulers::limit_index("ab\u{4e2d}c", 3, 4), Some(2));
+}
+
+// Test rendering the rulers and the text past the line length limit
+#[test]
+fn render_rulers() {
+	// Make an editor for the SMALL_FILE with a ruler and a line length limit at column 8
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.config.rulers = vec![8];
+	editor.config.line_limit = Some(8);
+	// Move off of the first line (so it isn't styled as the cursor's line)
+	down_arrow(&mut editor);
+
+	let buffer = render_buffer(&mut editor, 60, 6);
+	let theme = &editor.config.theme;
+	// The text starts after the line numbers widget and the editor's border
+	let text_x = 10;
+	// The ruler is drawn on every row of the text area
+	for y in 1..5 {
+		assert_eq!(buffer.get(text_x + 8, y).bg, theme.ruler_bg);
+	}
+	assert_ne!(buffer.get(text_x + 7, 1).bg, theme.ruler_bg);
+	// "#include<stdio.h>" is colored after its 8th column
+	assert_ne!(buffer.get(text_x + 7, 1).fg, theme.long_line_fg);
+	assert_eq!(buffer.get(text_x + 8, 1).fg, theme.long_line_fg);
+	assert_eq!(buffer.get(text_x + 16, 1).fg, theme.long_line_fg);
+}
+
+/*
+=======================================
+			RESIZE TESTS
+=======================================
+*/
+
+// Test that the cursor's line stays visible when the widget gets shorter
+#[test]
+fn resize_shorter() {
+	// Make an editor for the GENOME_FILE in a 20 row widget
+	let mut editor = EditorSpace::new(String::from(GENOME_FILE), Config::default());
+	render_editor(&mut editor, 60, 20);
+	assert_eq!(editor.height, 17);
+	// Move to the 16th line
+	for _i in 0..15 {
+		down_arrow(&mut editor);
+	}
+
+	// Shrink the widget to 10 rows
+	let rows = split_widgets(&render_editor(&mut editor, 60, 10));
+	assert_eq!(editor.height, 7);
+	assert_eq!(editor.cursor_position[1], 7);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 15);
+	// The cursor's line is shown on the last row of the widget
+	assert_eq!(rows[8].0, "16");
+}
+
+// Test that the text fills a taller widget (instead of leaving empty rows at the end)
+#[test]
+fn resize_taller() {
+	// Make an editor for the SMALL_FILE in an 8 row widget
+	let mut editor = EditorSpace::new(String::from(SMALL_FILE), Config::default());
+	render_editor(&mut editor, 60, 8);
+	// Move to the last line (scrolling the widget)
+	for _i in 0..12 {
+		down_arrow(&mut editor);
+	}
+	assert_eq!(editor.scroll_offset, 7);
+
+	// Grow the widget to 20 rows
+	let rows = split_widgets(&render_editor(&mut editor, 60, 20));
+	assert_eq!(editor.scroll_offset, 0);
+	assert_eq!(editor.cursor_position[1], 12);
+	assert_eq!(rows[1].0, "1");
+	assert_eq!(rows[13].0, "13");
+}
+
+// Test that the extra cursors (and their selections) are drawn over the text
+#[test]
+fn render_extra_cursors() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	// Add a cursor on the 3rd line and a cursor selecting "include"
+	editor
+		.cursors
+		.push(key_functions::multi_cursor::Cursor::new(2, 5));
+	editor
+		.cursors
+		.push(key_functions::multi_cursor::Cursor::selecting(0, 1, 8));
+
+	// The text area starts after the line numbers widget (and the border)
+	let buffer = render_buffer(&mut editor, 40, 10);
+	assert!(buffer.get(15, 3).modifier.contains(Modifier::REVERSED));
+	let highlight = editor.config.theme.selection_highlight;
+	assert_eq!(buffer.get(11, 1).bg, highlight);
+	assert_eq!(buffer.get(17, 1).bg, highlight);
+	assert_ne!(buffer.get(18, 1).bg, highlight);
+	// The selecting cursor is drawn at the end of its selection
+	assert!(buffer.get(18, 1).modifier.contains(Modifier::REVERSED));
+}
+
+// Test that a widget too small to show any text doesn't panic
+#[test]
+fn resize_tiny() {
+	// Make an editor for the SMALL_FILE in a 2 row widget (smaller than its borders)
+	let mut editor = EditorSpace::new(String::from(SMALL_FILE), Config::default());
+	render_editor(&mut editor, 10, 2);
+	assert_eq!(editor.height, 0);
+
+	// Grow the widget back to a usable size
+	render_editor(&mut editor, 60, 10);
+	assert_eq!(editor.height, 7);
+}
@@ -5,9 +5,15 @@
 */
 
 use super::*;
-use key_functions::{editing_keys::*, highlight_keys::*, navigation_keys::*, save_key::*, *};
+use config::config::{AutoIndent, ClipboardPreference};
+use key_functions::{
+	editing_keys::*, highlight_keys::*, indent::*, navigation_keys::*, save_key::*, *,
+};
 use serial_test::serial;
-use std::fs::{self, read_to_string};
+use std::{
+	collections::HashMap,
+	fs::{self, read_to_string},
+};
 use unredo_stack::stack_choice::StackChoice;
 
 /*
@@ -29,7 +35,7 @@ fn save_key_combo_small_file() {
 	// Ensure that the metadata of the file is up to date
 	editor.file.sync_all().unwrap();
 	// Get the number of bytes in this file
-	let original_file_length = editor.file.metadata().unwrap().len();
+	let original_file_length = editor.file.size().unwrap();
 
 	// Write the file to a different debug file
 	save_key_combo(&mut editor, true, debug_filename);
@@ -80,7 +86,7 @@ fn save_key_combo_genome_file() {
 	// Ensure that the metadata of the file is up to date
 	editor.file.sync_all().unwrap();
 	// Get the number of bytes in this file
-	let original_file_length = editor.file.metadata().unwrap().len();
+	let original_file_length = editor.file.size().unwrap();
 
 	// Write the file to a different debug file
 	save_key_combo(&mut editor, true, debug_filename);
@@ -132,7 +138,7 @@ fn save_key_combo_highlight_file() {
 	// Ensure that the metadata of the file is up to date
 	editor.file.sync_all().unwrap();
 	// Get the number of bytes in this file
-	let original_file_length = editor.file.metadata().unwrap().len();
+	let original_file_length = editor.file.size().unwrap();
 
 	/* Write the file to a different debug file.
 	Also, test that repeated saves don't change anything or
@@ -219,6 +225,94 @@ fn modified_small_file_save() {
 	fs::remove_file(debug_filename).unwrap();
 }
 
+/* Test saving a modified buffer that was piped in through stdin.
+The saved buffer should be written to the output rather than a file. */
+#[test]
+fn modified_piped_buffer_save() {
+	// Make an editor over an in-memory buffer (like one read from stdin)
+	let mut editor = EditorSpace::new(String::from(SMALL_FILE), Config::default());
+	editor.file = FileBacking::from_bytes(SMALL_FILE_BLOCK.as_bytes().to_vec());
+	editor.filename = String::from(STDIN_FILENAME);
+	let _ = editor.init_editor((0, 0), 500, 50);
+
+	// Check that the buffer was loaded like a file
+	assert!(editor.is_piped());
+	let actual_content = get_content(editor.blocks.as_ref().unwrap().clone());
+	let expected_content: Vec<&str> = SMALL_FILE_BLOCK.split('\n').collect();
+	assert_eq!(actual_content, expected_content);
+
+	// Make the same modifications as the modified_small_file_save test
+	for _i in 0..3 {
+		down_arrow(&mut editor);
+	}
+	home_key(&mut editor, true);
+	backspace(&mut editor);
+	home_key(&mut editor, true);
+	for _i in 0..3 {
+		down_arrow(&mut editor);
+	}
+	home_key(&mut editor, true);
+	backspace(&mut editor);
+
+	// Write the buffer to an output (which saves the edits to it first)
+	let mut output = Vec::new();
+	editor.write_buffer(&mut output).unwrap();
+
+	// Check that the modified buffer was saved correctly
+	assert_eq!(String::from_utf8(output).unwrap(), MODIFIED_SMALL_SAVE_FILE);
+	// Check that no file was created for the buffer
+	assert!(!Path::new(STDIN_FILENAME).exists());
+}
+
+/* Test opening and saving modified gzip and zstd compressed files.
+The files should be saved with the same compression format. */
+#[test]
+fn modified_compressed_file_save() {
+	for (idx, compression) in [Compression::Gzip, Compression::Zstd].iter().enumerate() {
+		// The filename of the compressed debug file
+		let debug_filename =
+			&(String::from(SMALL_FILE) + "-debug-test-compressed-" + &idx.to_string());
+		// Write a compressed copy of the SMALL_FILE
+		let compressed = compression.compress(SMALL_FILE_BLOCK.as_bytes()).unwrap();
+		fs::write(debug_filename, compressed).unwrap();
+
+		// Make an editor for the compressed file
+		let mut editor = construct_editor(debug_filename);
+		// Check that the compression format was detected
+		assert_eq!(editor.compression, Some(*compression));
+		// Check that the file was decompressed
+		let actual_content = get_content(editor.blocks.as_ref().unwrap().clone());
+		let expected_content: Vec<&str> = SMALL_FILE_BLOCK.split('\n').collect();
+		assert_eq!(actual_content, expected_content);
+
+		// Make the same modifications as the modified_small_file_save test
+		for _i in 0..3 {
+			down_arrow(&mut editor);
+		}
+		home_key(&mut editor, true);
+		backspace(&mut editor);
+		home_key(&mut editor, true);
+		for _i in 0..3 {
+			down_arrow(&mut editor);
+		}
+		home_key(&mut editor, true);
+		backspace(&mut editor);
+
+		// Save the file (recompressing it)
+		save_key_combo(&mut editor, false, "");
+
+		// Check that the saved file was compressed with the same format
+		let saved = fs::read(debug_filename).unwrap();
+		assert_eq!(Compression::detect(&saved), Some(*compression));
+		// Check that the modifications were saved correctly
+		let saved_text = String::from_utf8(compression.decompress(&saved).unwrap()).unwrap();
+		assert_eq!(saved_text, MODIFIED_SMALL_SAVE_FILE);
+
+		// Delete the debug file
+		fs::remove_file(debug_filename).unwrap();
+	}
+}
+
 // Test saving a modified large file
 #[test]
 fn modified_large_file_save() {
@@ -894,6 +988,45 @@ fn jump_down_test() {
 	}
 }
 
+// Test jumping straight to a line (without stepping through the lines in between)
+#[test]
+fn jump_to_test() {
+	// Make an editor for the GENOME_FILE (which has more than one block)
+	let mut editor = construct_editor(GENOME_FILE);
+	let text = std::fs::read_to_string(GENOME_FILE).unwrap();
+	let content: Vec<&str> = text.lines().collect();
+
+	// A line below the widget is shown on the bottom row (loading in the blocks before it)
+	jump_to(&mut editor, 300, 3);
+	assert_eq!(editor.cursor_position[1], editor.height);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 300);
+	assert_eq!(editor.text_position, 3);
+	// The tracked location is on the line
+	assert_eq!(
+		editor.blocks.as_ref().unwrap().get_current_line(),
+		content[300]
+	);
+
+	// A line within the widget doesn't scroll
+	let scroll_offset = editor.scroll_offset;
+	jump_to(&mut editor, 290, 0);
+	assert_eq!(editor.scroll_offset, scroll_offset);
+	assert_eq!(editor.cursor_position[1], editor.height - 10);
+	assert_eq!(
+		editor.blocks.as_ref().unwrap().get_current_line(),
+		content[290]
+	);
+
+	// A line above the widget is shown on the top row
+	jump_to(&mut editor, 10, 0);
+	assert_eq!(editor.cursor_position[1], 0);
+	assert_eq!(editor.get_line_num(0), 10);
+	assert_eq!(
+		editor.blocks.as_ref().unwrap().get_current_line(),
+		content[10]
+	);
+}
+
 /*
 =======================================
 			UNDO/REDO TESTS
@@ -1102,7 +1235,7 @@ fn copy_empty_selection() {
 	// Copy line to clipboard
 	copy_paste::copy_to_clipboard(&mut editor);
 	// Get contents of copy
-	let actual_content = editor.clipboard.unwrap().get_contents().unwrap();
+	let actual_content = editor.clipboard.get_contents();
 
 	assert_eq!(actual_content, "void test_func() {");
 }
@@ -1202,3 +1335,535 @@ fn cut_narrow_file() {
 
 	assert_eq!(actual_content, expected_content);
 }
+
+/*
+=========================================
+			PASTED TEXT TESTS
+=========================================
+*/
+
+// Test pasting multiple lines of text from the terminal (bracketed paste)
+#[test]
+fn paste_text_multiline() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	// Move after "void " on the 3rd line
+	down_arrow(&mut editor);
+	down_arrow(&mut editor);
+	for _i in 0..5 {
+		right_arrow(&mut editor, true);
+	}
+
+	// Paste text with CRLF line endings and unicode characters
+	copy_paste::paste_text(&mut editor, "\u{e9}a\r\nb\r\n\u{1f979}c");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "void \u{e9}a");
+	assert_eq!(content[3], "b");
+	assert_eq!(content[4], "\u{1f979}ctest_func() {");
+	assert_eq!(editor.file_length, 15);
+	// The cursor is after the pasted text
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 4);
+	assert_eq!(editor.text_position, "\u{1f979}c".len());
+	assert_eq!(editor.cursor_position[0], 3);
+
+	// The entire paste is undone at once
+	undo_redo(&mut editor, StackChoice::Undo);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "void test_func() {");
+	assert_eq!(
+		content[3],
+		"\tprintf(\"Testing the Blocks construction \u{1f979}\\n\");"
+	);
+}
+
+// Test that pasting over a selection replaces it
+#[test]
+fn paste_text_over_selection() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	// Highlight "#include"
+	for _i in 0..8 {
+		highlight_right(&mut editor);
+	}
+
+	copy_paste::paste_text(&mut editor, "#import");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "#import<stdio.h>");
+	assert!(editor.selection.is_empty);
+	assert_eq!(editor.text_position, 7);
+	// The block is modified (so it isn't unloaded before the pasted text is saved)
+	assert!(editor.blocks.as_ref().unwrap().blocks_list[0].is_modified);
+}
+
+// Test that pasting a single line marks the file as modified
+#[test]
+fn paste_text_single_line_modifies() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	assert!(!editor.is_modified());
+
+	copy_paste::paste_text(&mut editor, "abc");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "abc#include<stdio.h>");
+	assert!(editor.is_modified());
+}
+
+/*
+=========================================
+			CLIPBOARD FALLBACK TESTS
+=========================================
+*/
+
+// Construct an editor over the SMALL_FILE that uses the given clipboard
+fn construct_clipboard_editor(preference: ClipboardPreference) -> EditorSpace {
+	let config = Config {
+		clipboard: preference,
+		..Config::default()
+	};
+	let mut editor = EditorSpace::new(String::from(SMALL_FILE), config);
+	let _ = editor.init_editor((0, 0), 500, 50);
+
+	editor
+}
+
+// Test copying and pasting with only the internal clipboard
+#[test]
+fn internal_clipboard() {
+	// Make an editor that only uses the internal clipboard
+	let mut editor = construct_clipboard_editor(ClipboardPreference::Internal);
+
+	// Copy "#include"
+	for _i in 0..8 {
+		highlight_right(&mut editor);
+	}
+	copy_paste::copy_to_clipboard(&mut editor);
+	// Nothing is sent to the terminal
+	assert_eq!(editor.take_clipboard_sequence(), None);
+
+	// Paste at the end of the line (clearing the selection)
+	end_key(&mut editor, true);
+	editor.selection.is_empty = true;
+	copy_paste::paste_from_clipboard(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "#include<stdio.h>#include");
+}
+
+// Test that copying with OSC 52 sends the text to the terminal (and can still be pasted)
+#[test]
+fn osc52_clipboard() {
+	// Make an editor that uses the terminal's clipboard
+	let mut editor = construct_clipboard_editor(ClipboardPreference::Osc52);
+
+	// Copy the first line (empty selection)
+	copy_paste::copy_to_clipboard(&mut editor);
+	// The text is base64 encoded within the escape sequence
+	assert_eq!(
+		editor.take_clipboard_sequence(),
+		Some(String::from("\x1b]52;c;I2luY2x1ZGU8c3RkaW8uaD4=\x07"))
+	);
+	// The sequence is only written once
+	assert_eq!(editor.take_clipboard_sequence(), None);
+
+	// The copied line can be pasted from within the editor
+	copy_paste::paste_from_clipboard(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "#include<stdio.h>#include<stdio.h>");
+}
+
+/*
+=========================================
+		KILL RING AND REGISTER TESTS
+=========================================
+*/
+
+// Test cycling through the kill ring after pasting
+#[test]
+fn kill_ring_cycling() {
+	// Make an editor that only uses the internal clipboard
+	let mut editor = construct_clipboard_editor(ClipboardPreference::Internal);
+	// Copy the first two lines
+	highlight_down(&mut editor);
+	copy_paste::copy_to_clipboard(&mut editor);
+	editor.selection.is_empty = true;
+	// Copy the 3rd line
+	down_arrow(&mut editor);
+	copy_paste::copy_to_clipboard(&mut editor);
+	assert_eq!(editor.clipboard.kill_ring_len(), 2);
+
+	// Paste on the empty 2nd line
+	up_arrow(&mut editor);
+	editor.clipboard.start_input();
+	copy_paste::paste_from_clipboard(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "void test_func() {");
+
+	// Replace the paste with the older (multiline) copy
+	editor.clipboard.start_input();
+	copy_paste::paste_previous(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "#include<stdio.h>");
+	assert_eq!(content[2], "");
+	assert_eq!(content[3], "void test_func() {");
+	assert_eq!(editor.file_length, 14);
+
+	// Cycle back around to the most recent copy
+	editor.clipboard.start_input();
+	copy_paste::paste_previous(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "void test_func() {");
+	assert_eq!(content[2], "void test_func() {");
+	assert_eq!(editor.file_length, 13);
+
+	// After another input, the most recent copy is pasted instead of replacing the paste
+	editor.clipboard.start_input();
+	editor.clipboard.start_input();
+	copy_paste::paste_previous(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "void test_func() {void test_func() {");
+}
+
+// Test yanking to and pasting from named registers
+#[test]
+fn named_registers() {
+	// Make an editor that only uses the internal clipboard
+	let mut editor = construct_clipboard_editor(ClipboardPreference::Internal);
+	// Yank the first line to register a
+	input_handlers::register_key(&mut editor, RegisterAction::Yank, KeyCode::Char('a'));
+	// Registers aren't part of the kill ring
+	assert_eq!(editor.clipboard.kill_ring_len(), 0);
+
+	// Paste register a on the empty 2nd line
+	down_arrow(&mut editor);
+	input_handlers::register_key(&mut editor, RegisterAction::Paste, KeyCode::Char('a'));
+	// Pasting an empty register does nothing
+	input_handlers::register_key(&mut editor, RegisterAction::Paste, KeyCode::Char('b'));
+	// Registers are only named by letters
+	input_handlers::register_key(&mut editor, RegisterAction::Paste, KeyCode::Char('1'));
+
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "#include<stdio.h>");
+	assert_eq!(editor.file_length, 13);
+}
+
+/*
+==================================
+			AUTO INDENT TESTS
+==================================
+*/
+
+// Type each character of the text
+fn type_chars(editor: &mut EditorSpace, text: &str) {
+	for code in text.chars() {
+		char_key(editor, code);
+	}
+}
+
+// Test indenting new lines after opening brackets (and de-indenting closing brackets)
+#[test]
+fn smart_indent() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// A new line after an opening bracket is indented one level further
+	jump_to(&mut editor, 2, usize::MAX);
+	enter_key(&mut editor);
+	assert_eq!(editor.text_position, 1);
+	assert_eq!(editor.cursor_position[0], editor.config.tab_width);
+	// A new line keeps the indentation of the line
+	type_chars(&mut editor, "int x = 0;");
+	enter_key(&mut editor);
+	// Typing a closing bracket at the start of the line de-indents it
+	type_chars(&mut editor, "}");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[3], "\tint x = 0;");
+	assert_eq!(content[4], "}");
+
+	// A new line between a pair of brackets moves the closing bracket below it
+	jump_to(&mut editor, 14, 0);
+	type_chars(&mut editor, "f() {}");
+	left_arrow(&mut editor, true);
+	enter_key(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[14..], ["f() {", "\t", "}"]);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 15);
+	assert_eq!(editor.text_position, 1);
+}
+
+// Test the auto indent of a language and turning auto indent off
+#[test]
+fn language_indent() {
+	// Python indents after a colon (and only keeps the indentation of Markdown)
+	let config = Config {
+		language_indent: HashMap::from([(String::from("Markdown"), AutoIndent::Keep)]),
+		..Config::default()
+	};
+	let mut editor = EditorSpace::new(String::from(SMALL_FILE), config);
+	let _ = editor.init_editor((0, 0), 500, 50);
+	editor.language = Some(Language::Python);
+	type_chars(&mut editor, "if x:");
+	enter_key(&mut editor);
+	assert_eq!(editor.text_position, 1);
+	editor.language = Some(Language::Markdown);
+	type_chars(&mut editor, "[");
+	enter_key(&mut editor);
+	assert_eq!(editor.text_position, 1);
+
+	// With auto indent off, new lines aren't indented
+	editor.config.auto_indent = AutoIndent::Off;
+	editor.language = None;
+	jump_to(&mut editor, 6, usize::MAX);
+	enter_key(&mut editor);
+	assert_eq!(editor.text_position, 0);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[7], "");
+}
+
+// Test detecting whether a file is indented with tabs or spaces
+#[test]
+fn detect_file_indentation() {
+	// The SPACES_FILE is indented with two spaces
+	let editor = construct_editor(SPACES_FILE);
+	assert_eq!(editor.indentation, Indentation::Spaces(2));
+	assert_eq!(editor.indent_mode(), "Spaces: 2");
+	// The SMALL_FILE is indented with tabs
+	let editor = construct_editor(SMALL_FILE);
+	assert_eq!(editor.indentation, Indentation::Tabs);
+
+	// Files without indentation are indented as set by the config
+	let config = Config {
+		soft_tabs: true,
+		..Config::default()
+	};
+	let mut editor = EditorSpace::new(String::from(HIGHLIGHT_FILE), config);
+	let _ = editor.init_editor((0, 0), 500, 50);
+	assert_eq!(editor.indentation, Indentation::Spaces(4));
+}
+
+// Test indenting with spaces and backspacing over a level of indentation
+#[test]
+fn soft_tabs() {
+	// Make an editor for the SPACES_FILE
+	let mut editor = construct_editor(SPACES_FILE);
+
+	// Tab inserts spaces up to the next level of indentation
+	jump_to(&mut editor, 1, 2);
+	tab_key(&mut editor);
+	jump_to(&mut editor, 4, 0);
+	char_key(&mut editor, ' ');
+	tab_key(&mut editor);
+	assert_eq!(editor.text_position, 2);
+
+	// Backspace removes the spaces back to the previous level
+	backspace(&mut editor);
+	assert_eq!(editor.text_position, 0);
+	jump_to(&mut editor, 2, 4);
+	backspace(&mut editor);
+	// Backspace after the indentation removes a single character
+	jump_to(&mut editor, 3, 4);
+	backspace(&mut editor);
+
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "    if True:");
+	assert_eq!(content[2], "  print(\"Indented with two spaces\")");
+	assert_eq!(content[3], "  rturn 0");
+	assert_eq!(content[4], "");
+}
+
+// Test converting the indentation of the file (or selection) between tabs and spaces
+#[test]
+fn convert_file_indentation() {
+	// Make an editor for the SPACES_FILE
+	let mut editor = construct_editor(SPACES_FILE);
+
+	// Convert the entire file to tabs (keeping the cursor on its text)
+	jump_to(&mut editor, 2, 5);
+	convert_indentation(&mut editor);
+	assert_eq!(editor.indent_mode(), "Tabs: 4");
+	assert_eq!(editor.text_position, 3);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "\tif True:");
+	assert_eq!(content[2], "\t\tprint(\"Indented with two spaces\")");
+
+	// Convert only the selected line back to spaces
+	jump_to(&mut editor, 1, 0);
+	highlight_right(&mut editor);
+	convert_indentation(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "    if True:");
+	assert_eq!(content[2], "\t\tprint(\"Indented with two spaces\")");
+
+	// The conversion is undone at once
+	undo_redo(&mut editor, StackChoice::Undo);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "\tif True:");
+
+	// A selection ending at the start of a line doesn't convert that line
+	let mut editor = construct_editor(SPACES_FILE);
+	select_range(&mut editor, [0, 1], [0, 3]);
+	convert_indentation(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "\tif True:");
+	assert_eq!(content[2], "\t\tprint(\"Indented with two spaces\")");
+	assert_eq!(content[3], "  return 0");
+
+	// A tab after spaces only moves the indentation to the next tab stop
+	let mut editor = construct_editor(SPACES_FILE);
+	editor
+		.blocks
+		.as_mut()
+		.unwrap()
+		.update_some_line(String::from(" \treturn 0"), 3)
+		.unwrap();
+	convert_indentation(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[3], "\treturn 0");
+}
+
+// Test indenting and outdenting the selected lines (keeping the selection)
+#[test]
+fn indent_selected_lines() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Indent the lines of main (the selection moves with its text)
+	select_range(&mut editor, [2, 6], [3, 8]);
+	indent_lines(&mut editor, false);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[6], "\tint main() {");
+	assert!(content[7].starts_with("\t\tprintf("));
+	assert_eq!(content[8], "\t\ttest_func();");
+	assert_eq!(editor.selection.start, [3, 6]);
+	assert_eq!(editor.selection.end, [4, 8]);
+	// The indentation is undone at once
+	undo_redo(&mut editor, StackChoice::Undo);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[6], "int main() {");
+	assert_eq!(content[8], "\ttest_func();");
+
+	// Outdent the same lines (lines without indentation don't change)
+	select_range(&mut editor, [2, 6], [3, 8]);
+	indent_lines(&mut editor, true);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[6], "int main() {");
+	assert!(content[7].starts_with("printf("));
+	assert_eq!(content[8], "test_func();");
+	assert_eq!(editor.selection.start, [2, 6]);
+	assert_eq!(editor.selection.end, [2, 8]);
+
+	// Empty lines and the line after a selection ending at its start aren't indented
+	select_range(&mut editor, [0, 0], [0, 3]);
+	indent_lines(&mut editor, false);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(
+		content[0..3],
+		["\t#include<stdio.h>", "", "\tvoid test_func() {"]
+	);
+	assert!(!content[3].starts_with("\t\t"));
+	assert_eq!(editor.selection.start, [0, 0]);
+	assert_eq!(editor.selection.end, [0, 3]);
+}
+
+// Test that indenting (or converting) lines removes the extra cursors
+#[test]
+fn indent_lines_clears_cursors() {
+	// Make an editor for the SMALL_FILE with an extra cursor
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.cursors.push(multi_cursor::Cursor::new(8, 0));
+
+	// Only the cursor's line is outdented
+	jump_to(&mut editor, 7, 0);
+	indent_lines(&mut editor, true);
+	assert!(editor.cursors.is_empty());
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert!(content[7].starts_with("printf("));
+	assert_eq!(content[8], "\ttest_func();");
+
+	// Converting the indentation also removes them
+	editor.cursors.push(multi_cursor::Cursor::new(8, 0));
+	convert_indentation(&mut editor);
+	assert!(editor.cursors.is_empty());
+}
+
+/*
+==================================
+			AUTO PAIR TESTS
+==================================
+*/
+
+// Test closing brackets and quotes, typing over the closers, and deleting empty pairs
+#[test]
+fn auto_pair_brackets() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// The closer is inserted after the cursor
+	jump_to(&mut editor, 12, 0);
+	type_chars(&mut editor, "foo(");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[12], "foo()");
+	assert_eq!(editor.text_position, 4);
+	// Typing the closer moves over it
+	type_chars(&mut editor, "a)");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[12], "foo(a)");
+	assert_eq!(editor.text_position, 6);
+
+	// Backspace in an empty pair deletes both characters
+	type_chars(&mut editor, " [");
+	backspace(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[12], "foo(a) ");
+	// Quotes are closed (and typed over)
+	type_chars(&mut editor, "\"b\"");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[12], "foo(a) \"b\"");
+
+	// Openers followed by text and apostrophes in words aren't closed
+	jump_to(&mut editor, 1, 0);
+	type_chars(&mut editor, "don't");
+	home_key(&mut editor, true);
+	type_chars(&mut editor, "(");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1], "(don't");
+
+	// Nothing is paired with auto pairing disabled
+	editor.config.auto_pair = false;
+	jump_to(&mut editor, 5, 0);
+	type_chars(&mut editor, "{");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[5], "{");
+}
+
+// Test surrounding the selection with brackets (or quotes)
+#[test]
+fn auto_pair_surround_selection() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// The selected text is surrounded (and stays selected)
+	select_range(&mut editor, [1, 8], [12, 8]);
+	char_key(&mut editor, '(');
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[8], "\t(test_func());");
+	assert_eq!(editor.selection.start, [2, 8]);
+	assert_eq!(editor.selection.end, [13, 8]);
+	// Surround it again with quotes
+	char_key(&mut editor, '"');
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[8], "\t(\"test_func()\");");
+
+	// Each surround is a single undo state
+	undo_redo(&mut editor, StackChoice::Undo);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[8], "\t(test_func());");
+
+	// A selection over multiple lines
+	editor.selection.is_empty = true;
+	select_range(&mut editor, [5, 2], [1, 4]);
+	char_key(&mut editor, '[');
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "void [test_func() {");
+	assert_eq!(content[4], "}]");
+	assert_eq!(editor.selection.start, [6, 2]);
+	assert_eq!(editor.selection.end, [1, 4]);
+}
@@ -0,0 +1,160 @@
+/*
+=========================================
+			LINE KEYS TESTS
+=========================================
+*/
+
+use super::*;
+use config::config::ClipboardPreference;
+use key_functions::{
+	highlight_keys::select_range, line_keys::*, navigation_keys::jump_to, select_keys, undo_redo,
+};
+use unredo_stack::stack_choice::StackChoice;
+
+// Test moving the cursor's line (and the selected lines) up and down
+#[test]
+fn move_lines() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Move the line up (the cursor moves with it)
+	jump_to(&mut editor, 8, 1);
+	move_lines_up(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[7], "\ttest_func();");
+	assert!(content[8].starts_with("\tprintf(\"Hopefully"));
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 7);
+	assert_eq!(editor.text_position, 1);
+
+	// Move the selected function up past the empty line above it
+	select_range(&mut editor, [0, 2], [0, 5]);
+	move_lines_up(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(
+		content[1..5],
+		["void test_func() {", content[2].as_str(), "}", ""]
+	);
+	assert_eq!(editor.selection.start, [0, 1]);
+	assert_eq!(editor.selection.end, [0, 4]);
+	// And back down
+	move_lines_down(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[1..3], ["", "void test_func() {"]);
+	assert_eq!(editor.file_length, 13);
+
+	// Lines can't be moved past the start of the file
+	jump_to(&mut editor, 0, 0);
+	editor.selection.is_empty = true;
+	move_lines_up(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "#include<stdio.h>");
+}
+
+// Test moving a line from one block into the next block
+#[test]
+fn move_line_across_blocks() {
+	// Make an editor for the GENOME_FILE
+	let mut editor = construct_editor(GENOME_FILE);
+	// The last line of the first block
+	let line_num = editor.blocks.as_ref().unwrap().blocks_list[0].len - 1;
+	jump_to(&mut editor, line_num + 1, 0);
+	let (line, next) = {
+		let blocks = editor.blocks.as_ref().unwrap();
+		(
+			blocks.get_some_line(line_num).unwrap(),
+			blocks.get_some_line(line_num + 1).unwrap(),
+		)
+	};
+
+	// Move the line down into the second block
+	jump_to(&mut editor, line_num, 0);
+	move_lines_down(&mut editor);
+	let blocks = editor.blocks.as_ref().unwrap();
+	assert_eq!(blocks.get_some_line(line_num).unwrap(), next);
+	assert_eq!(blocks.get_some_line(line_num + 1).unwrap(), line);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), line_num + 1);
+}
+
+// Test duplicating the selected lines
+#[test]
+fn duplicate_selected_lines() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Duplicate the function (the copy is selected)
+	select_range(&mut editor, [0, 2], [0, 5]);
+	duplicate_lines(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2..5], content[5..8]);
+	assert_eq!(content[8], "");
+	assert_eq!(editor.file_length, 16);
+	assert_eq!(editor.selection.start, [0, 5]);
+	assert_eq!(editor.selection.end, [0, 8]);
+
+	// Undoing restores the length of the file
+	undo_redo(&mut editor, StackChoice::Undo);
+	assert_eq!(editor.file_length, 13);
+}
+
+// Test joining lines (collapsing the whitespace between them)
+#[test]
+fn join_selected_lines() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Join the next line onto the cursor's line
+	jump_to(&mut editor, 2, 0);
+	join_lines(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert!(content[2].starts_with("void test_func() { printf(\"Testing"));
+	assert_eq!(editor.text_position, 19);
+	assert_eq!(editor.file_length, 12);
+
+	// Join the selected lines
+	select_range(&mut editor, [0, 5], [3, 7]);
+	join_lines(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert!(content[5].starts_with("int main() { printf(\"Hopefully"));
+	assert!(content[5].ends_with("\"); test_func();"));
+	assert_eq!(content[6], "");
+	assert_eq!(editor.file_length, 10);
+}
+
+// Test deleting lines (into the kill ring)
+#[test]
+fn delete_selected_lines() {
+	// Make an editor for the SMALL_FILE (with the internal clipboard)
+	let config = Config {
+		clipboard: ClipboardPreference::Internal,
+		..Config::default()
+	};
+	let mut editor = EditorSpace::new(String::from(SMALL_FILE), config);
+	let _ = editor.init_editor((0, 0), 500, 50);
+
+	// Delete the cursor's line
+	jump_to(&mut editor, 3, 2);
+	delete_lines(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2..4], ["void test_func() {", "}"]);
+	assert_eq!(editor.file_length, 12);
+	assert_eq!(editor.get_line_num(editor.cursor_position[1]), 3);
+	// The deleted line is in the kill ring
+	let line = editor.clipboard.get_contents();
+	assert!(line.starts_with("\tprintf(\"Testing") && line.ends_with(";\n"));
+
+	// Delete the last line of the file
+	jump_to(&mut editor, 11, 0);
+	delete_lines(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[10], "}");
+	assert_eq!(editor.file_length, 11);
+
+	// Delete every line
+	select_keys::select_all(&mut editor);
+	delete_lines(&mut editor);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content, vec![String::new()]);
+	assert_eq!(editor.file_length, 1);
+	undo_redo(&mut editor, StackChoice::Undo);
+	assert_eq!(editor.file_length, 11);
+}
@@ -0,0 +1,147 @@
+/*
+===================================
+			MOUSE TESTS
+===================================
+*/
+
+use super::*;
+use key_functions::{mouse::*, navigation_keys::down_arrow};
+
+/* The editor from `construct_editor` starts at the top left of the terminal.
+Its text area starts after the borders (at column 1 and row 1). */
+fn click_text(editor: &mut EditorSpace, column: usize, row: usize) {
+	click(editor, column as u16 + 1, row as u16 + 1);
+}
+
+// Get the (text position, line number) of the cursor
+fn cursor_location(editor: &EditorSpace) -> (usize, usize) {
+	(
+		editor.text_position,
+		editor.get_line_num(editor.cursor_position[1]),
+	)
+}
+
+// Test that clicking moves the cursor (accounting for tabs and wide graphemes)
+#[test]
+fn click_moves_cursor() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Click on the 'i' of "printf" (after a tab)
+	click_text(&mut editor, 6, 3);
+	assert_eq!(cursor_location(&editor), (3, 3));
+	assert_eq!(editor.cursor_position, [6, 3]);
+	// Clicking within a tab moves to the start of the tab
+	click_text(&mut editor, 2, 3);
+	assert_eq!(editor.cursor_position, [0, 3]);
+	// Clicking on the second column of the emoji moves to the start of it
+	click_text(&mut editor, 45, 3);
+	assert_eq!(cursor_location(&editor), (41, 3));
+	assert_eq!(editor.cursor_position, [44, 3]);
+
+	// Clicking after the end of a line moves to the end of the line
+	click_text(&mut editor, 100, 2);
+	assert_eq!(cursor_location(&editor), (18, 2));
+	// Clicking below the end of the file moves to the last line
+	click_text(&mut editor, 0, 40);
+	assert_eq!(cursor_location(&editor), (0, 12));
+
+	// Clicking outside of the text area doesn't move the cursor
+	click(&mut editor, 0, 5);
+	assert_eq!(cursor_location(&editor), (0, 12));
+}
+
+// Test that dragging highlights from the clicked text
+#[test]
+fn drag_highlights() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Drag from the start of the 3rd line to after the tab on the 4th line
+	click_text(&mut editor, 0, 2);
+	drag(&mut editor, 5, 4);
+	assert!(!editor.selection.is_empty);
+	assert_eq!(editor.selection.start, [0, 2]);
+	assert_eq!(editor.selection.end, [1, 3]);
+
+	// Dragging above the clicked text highlights backwards
+	drag(&mut editor, 6, 1);
+	assert_eq!(editor.selection.start, [5, 0]);
+	assert_eq!(editor.selection.end, [0, 2]);
+
+	// Dragging back to the clicked text clears the selection
+	drag(&mut editor, 1, 3);
+	assert!(editor.selection.is_empty);
+}
+
+// Test that double clicks select a word and triple clicks select a line
+#[test]
+fn multiple_clicks() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Double click "test_func"
+	click_text(&mut editor, 7, 2);
+	click_text(&mut editor, 7, 2);
+	assert_eq!(editor.selection.start, [5, 2]);
+	assert_eq!(editor.selection.end, [14, 2]);
+	assert_eq!(cursor_location(&editor), (14, 2));
+
+	// Triple click selects the entire line
+	click_text(&mut editor, 7, 2);
+	assert_eq!(editor.selection.start, [0, 2]);
+	assert_eq!(editor.selection.end, [0, 3]);
+
+	// Clicking somewhere else is a single click
+	click_text(&mut editor, 3, 0);
+	assert!(editor.selection.is_empty);
+	assert_eq!(cursor_location(&editor), (3, 0));
+}
+
+// Test that the mouse wheel scrolls the text (loading blocks as needed)
+#[test]
+fn wheel_scrolling() {
+	// Make an editor for the GENOME_FILE
+	let mut editor = construct_editor(GENOME_FILE);
+	// Put the cursor on the 11th line
+	click_text(&mut editor, 0, 10);
+
+	// The cursor stays on its line while scrolling
+	scroll_down(&mut editor);
+	assert_eq!(editor.scroll_offset, 3);
+	assert_eq!(editor.cursor_position[1], 7);
+	assert_eq!(cursor_location(&editor), (0, 10));
+
+	// The cursor moves with the top of the widget once its line is scrolled past
+	for _i in 0..110 {
+		scroll_down(&mut editor);
+	}
+	// The last line of the file is at the top of the widget
+	assert_eq!(editor.get_line_num(0), editor.file_length - 1);
+	assert_eq!(editor.cursor_position[1], 0);
+	// The cursor's line matches the line reached with the arrow keys
+	let mut other = construct_editor(GENOME_FILE);
+	for _i in 0..editor.file_length - 1 {
+		down_arrow(&mut other);
+	}
+	assert_eq!(
+		editor.blocks.as_ref().unwrap().get_current_line(),
+		other.blocks.as_ref().unwrap().get_current_line()
+	);
+
+	// Scroll back to the top of the file
+	for _i in 0..120 {
+		scroll_up(&mut editor);
+	}
+	assert_eq!(editor.get_line_num(0), 0);
+	assert_eq!(editor.cursor_position[1], editor.height);
+	assert_eq!(
+		editor.blocks.as_ref().unwrap().get_current_line(),
+		editor
+			.blocks
+			.as_ref()
+			.unwrap()
+			.get_some_line(editor.height)
+			.unwrap()
+	);
+}
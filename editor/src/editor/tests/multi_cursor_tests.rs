@@ -0,0 +1,189 @@
+/*
+==========================================
+			MULTI CURSOR TESTS
+==========================================
+*/
+
+use super::*;
+use key_functions::{
+	editing_keys::*,
+	highlight_keys::select_range,
+	multi_cursor::{self, *},
+	navigation_keys::jump_to,
+	undo_redo,
+};
+use unredo_stack::stack_choice::StackChoice;
+
+// Get the (line number, text position) of the primary cursor and each extra cursor
+fn cursor_locations(editor: &EditorSpace) -> Vec<(usize, usize)> {
+	let mut locations = vec![(
+		editor.get_line_num(editor.cursor_position[1]),
+		editor.text_position,
+	)];
+	locations.extend(
+		editor
+			.cursors
+			.iter()
+			.map(|cursor| (cursor.line_num, cursor.text_position)),
+	);
+	locations
+}
+
+// Type a string of text at every cursor
+fn type_text(editor: &mut EditorSpace, text: &str) {
+	for character in text.chars() {
+		multi_cursor::edit(editor, false, |editor| char_key(editor, character));
+	}
+}
+
+// Test typing at cursors added below the primary cursor (and undoing it all at once)
+#[test]
+fn typing_at_cursors_below() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	// Add cursors on the next two lines
+	add_cursor_below(&mut editor);
+	add_cursor_below(&mut editor);
+	assert_eq!(cursor_locations(&editor), vec![(0, 0), (1, 0), (2, 0)]);
+
+	// Type at every cursor
+	type_text(&mut editor, "//");
+	multi_cursor::edit(&mut editor, false, backspace);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "/#include<stdio.h>");
+	assert_eq!(content[1], "/");
+	assert_eq!(content[2], "/void test_func() {");
+	assert_eq!(cursor_locations(&editor), vec![(0, 1), (1, 1), (2, 1)]);
+
+	// The edits at every cursor are undone together (which removes the extra cursors)
+	undo_redo(&mut editor, StackChoice::Undo);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[0], "#include<stdio.h>");
+	assert_eq!(content[1], "");
+	assert_eq!(content[2], "void test_func() {");
+	assert!(editor.cursors.is_empty());
+}
+
+// Test replacing every occurrence of a word and splitting the lines at each cursor
+#[test]
+fn editing_all_occurrences() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	// Select every "printf" (the word at the cursor)
+	jump_to(&mut editor, 3, 1);
+	add_all_occurrences(&mut editor);
+	assert_eq!(editor.cursors.len(), 1);
+
+	// Replace the selections
+	type_text(&mut editor, "puts");
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert!(content[3].starts_with("\tputs(\"Testing"));
+	assert!(content[7].starts_with("\tputs(\"Hopefully"));
+	assert_eq!(cursor_locations(&editor), vec![(3, 5), (7, 5)]);
+
+	// Insert a new line at each cursor (keeping the indentation of the line)
+	multi_cursor::edit(&mut editor, true, enter_key);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[3], "\tputs");
+	assert!(content[4].starts_with("\t(\"Testing"));
+	assert_eq!(content[8], "\tputs");
+	assert!(content[9].starts_with("\t(\"Hopefully"));
+	assert_eq!(editor.file_length, 15);
+	assert_eq!(cursor_locations(&editor), vec![(4, 1), (9, 1)]);
+}
+
+// Test editing at multiple cursors on the same line
+#[test]
+fn cursors_on_one_line() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	// Add cursors before and after "test_func" on the 3rd line
+	jump_to(&mut editor, 2, 5);
+	editor.cursors.push(Cursor::new(2, 14));
+
+	// Split the line at both cursors
+	multi_cursor::edit(&mut editor, true, enter_key);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "void ");
+	assert_eq!(content[3], "test_func");
+	assert_eq!(content[4], "() {");
+	assert_eq!(editor.file_length, 15);
+	assert_eq!(cursor_locations(&editor), vec![(3, 0), (4, 0)]);
+
+	// Join the lines back together
+	multi_cursor::edit(&mut editor, false, backspace);
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content[2], "void test_func() {");
+	assert_eq!(editor.file_length, 13);
+	assert_eq!(cursor_locations(&editor), vec![(2, 5), (2, 14)]);
+}
+
+// Test adding cursors at the next occurrences of the selected word
+#[test]
+fn next_occurrence() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	jump_to(&mut editor, 2, 5);
+
+	// The first time selects the word at the cursor
+	add_next_occurrence(&mut editor);
+	assert!(editor.cursors.is_empty());
+	assert_eq!(editor.selection.start, [5, 2]);
+	assert_eq!(editor.selection.end, [14, 2]);
+
+	// Then the next occurrence of the word is selected by a new cursor
+	add_next_occurrence(&mut editor);
+	assert_eq!(editor.cursors.len(), 1);
+	assert_eq!(editor.cursors[0].selection.start, [1, 8]);
+	assert_eq!(editor.cursors[0].selection.end, [10, 8]);
+	// There are no more occurrences
+	add_next_occurrence(&mut editor);
+	assert_eq!(editor.cursors.len(), 1);
+}
+
+// Test that editing at cursors near the end of a long file only keeps the blocks around them loaded
+#[test]
+fn cursors_in_later_blocks() {
+	// Make an editor for the GENOME_FILE (which has more than one block)
+	let mut editor = construct_editor(GENOME_FILE);
+	// Select the first two characters of several lines near the end of the file
+	select_range(&mut editor, [0, 300], [2, 300]);
+	for line_num in 301..306 {
+		editor.cursors.push(Cursor::selecting(line_num, 0, 2));
+	}
+
+	// Replace the selections
+	type_text(&mut editor, "x");
+	let blocks = editor.blocks.as_ref().unwrap();
+	assert!(blocks.get_some_line(300).unwrap().starts_with('x'));
+	assert!(blocks.get_some_line(305).unwrap().starts_with('x'));
+	assert_eq!(cursor_locations(&editor)[..2], [(300, 1), (301, 1)]);
+	// The blocks before the cursors were unloaded
+	assert!(blocks.head_block > 0);
+	assert!(blocks.blocks_list.len() <= 3);
+}
+
+// Test that only the first occurrences get a cursor when there are too many of them
+#[test]
+fn occurrence_limit() {
+	// Write more occurrences of a word than the number of cursors allowed to a test file
+	let filename = "../editor/test_files/occurrence_limit-debug-test.txt";
+	std::fs::write(filename, "word\n".repeat(MAX_CURSORS + 10)).unwrap();
+	// Make an editor for the test file
+	let mut editor = construct_editor(filename);
+	std::fs::remove_file(filename).unwrap();
+
+	// Select every "word"
+	add_all_occurrences(&mut editor);
+	add_all_occurrences(&mut editor);
+	assert_eq!(editor.cursors.len(), MAX_CURSORS - 1);
+	assert_eq!(editor.cursors.last().unwrap().line_num, MAX_CURSORS - 1);
+	assert_eq!(
+		editor.status_message.as_ref().unwrap().text,
+		format!(
+			"Limited to {} cursors (of {} occurrences)",
+			MAX_CURSORS,
+			MAX_CURSORS + 10
+		)
+	);
+}
@@ -4,7 +4,9 @@
 =================================================
 */
 
-use key_functions::{editing_keys::*, highlight_keys::*, navigation_keys::*};
+use key_functions::{
+	copy_paste, editing_keys::*, highlight_keys::*, navigation_keys::*, select_keys,
+};
 
 use super::*;
 
@@ -381,3 +383,100 @@ fn jump_down_selection() {
 	let actual_content = get_content(editor.blocks.as_ref().unwrap().clone());
 	assert_eq!(actual_content, vec![""]);
 }
+
+// Test selecting the entire file (across all of its blocks)
+#[test]
+fn select_all_blocks() {
+	// Make an editor for the GENOME_FILE
+	let mut editor = construct_editor(GENOME_FILE);
+	select_keys::select_all(&mut editor);
+
+	// The selection is from the start to the end of the file
+	assert_eq!(editor.selection.start, [0, 0]);
+	assert_eq!(editor.selection.end[1], editor.file_length - 1);
+	// Every line of the file is copied
+	let text = std::fs::read_to_string(GENOME_FILE).unwrap();
+	assert_eq!(copy_paste::copy_text(&mut editor), text);
+
+	// Deleting the selection empties the file
+	editor.delete_selection();
+	let content = get_content(editor.blocks.as_ref().unwrap().clone());
+	assert_eq!(content, vec![String::new()]);
+	assert_eq!(editor.file_length, 1);
+}
+
+// Test selecting the word and the line at the cursor
+#[test]
+fn select_word_and_line() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+
+	// Select the word in the middle of the 3rd line
+	jump_to(&mut editor, 2, 7);
+	select_keys::select_word(&mut editor);
+	assert_eq!(copy_paste::copy_text(&mut editor), "test_func");
+	// Select the entire line
+	select_keys::select_line(&mut editor);
+	assert_eq!(copy_paste::copy_text(&mut editor), "void test_func() {\n");
+}
+
+// Test expanding the selection outward (and shrinking it back)
+#[test]
+fn expand_and_shrink_selection() {
+	// Make an editor for the SMALL_FILE
+	let mut editor = construct_editor(SMALL_FILE);
+	jump_to(&mut editor, 3, 10);
+
+	// The selection grows through the word, quotes, brackets, line, paragraph, and file
+	let expected = [
+		"Testing",
+		"Testing the Blocks construction 🥹\\n",
+		"\"Testing the Blocks construction 🥹\\n\"",
+		"(\"Testing the Blocks construction 🥹\\n\")",
+		"\tprintf(\"Testing the Blocks construction 🥹\\n\");",
+		"\n\tprintf(\"Testing the Blocks construction 🥹\\n\");\n",
+		"{\n\tprintf(\"Testing the Blocks construction 🥹\\n\");\n}",
+		"void test_func() {\n\tprintf(\"Testing the Blocks construction 🥹\\n\");\n}",
+	];
+	for text in expected {
+		select_keys::expand_selection(&mut editor);
+		assert_eq!(copy_paste::copy_text(&mut editor), text);
+	}
+	select_keys::expand_selection(&mut editor);
+	assert_eq!(editor.selection.start, [0, 0]);
+	assert_eq!(editor.selection.end, [0, 12]);
+
+	// Shrinking goes back through the same selections
+	for text in expected.iter().rev() {
+		select_keys::shrink_selection(&mut editor);
+		assert_eq!(copy_paste::copy_text(&mut editor), *text);
+	}
+	// Until only the cursor is left
+	select_keys::shrink_selection(&mut editor);
+	assert!(editor.selection.is_empty);
+	assert_eq!(editor.text_position, 10);
+}
+
+// Test expanding the selection near the end of a long file (up to the entire file)
+#[test]
+fn expand_selection_in_later_block() {
+	// Make an editor for the GENOME_FILE
+	let mut editor = construct_editor(GENOME_FILE);
+	jump_to(&mut editor, 300, 10);
+
+	// The word on the line is selected without loading the start of the file
+	select_keys::expand_selection(&mut editor);
+	assert_eq!(editor.selection.start, [0, 300]);
+	assert_eq!(editor.selection.end, [80, 300]);
+	assert!(editor.blocks.as_ref().unwrap().head_block > 0);
+
+	// The file has no blank lines, so its paragraph is the entire file
+	select_keys::expand_selection(&mut editor);
+	assert_eq!(editor.selection.start, [0, 0]);
+	assert_eq!(editor.selection.end[1], 319);
+	select_keys::expand_selection(&mut editor);
+	assert_eq!(
+		editor.status_message.as_ref().unwrap().text,
+		"The entire file is selected"
+	);
+}
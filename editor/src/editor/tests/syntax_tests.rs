@@ -0,0 +1,411 @@
+/*
+=====================================
+			SYNTAX TESTS
+=====================================
+*/
+
+use super::*;
+use key_functions::{editing_keys::*, navigation_keys::*};
+use syntax::{SyntaxState, TokenKind};
+
+// Highlight a line and return the text of each token with its kind
+fn tokens_of(language: Language, line: &str, state: SyntaxState) -> Vec<(&str, TokenKind)> {
+	syntax::highlight_line(language, line, state)
+		.tokens
+		.iter()
+		.map(|token| (&line[token.start..token.end], token.kind))
+		.collect()
+}
+
+// Highlight the lines of a Blocks as Rust (using the generic lexer)
+fn highlight_blocks(blocks: &mut Blocks) {
+	blocks.update_highlights(|_, line, state| syntax::highlight_line(Language::Rust, line, state));
+}
+
+// Test detecting the language of a file by its extension or shebang
+#[test]
+fn detect_language() {
+	let detected = [
+		("src/main.rs", "", Some(Language::Rust)),
+		("Cargo.toml", "", Some(Language::Toml)),
+		("README.md", "# Title", Some(Language::Markdown)),
+		("setup.py", "", Some(Language::Python)),
+		("build.sh", "", Some(Language::Shell)),
+		("data.json", "", Some(Language::Json)),
+		("schema.sql", "", Some(Language::Sql)),
+		// Compressed files use the extension before the compression's extension
+		("data.json.gz", "", Some(Language::Json)),
+		// Files without an extension use their shebang
+		("script", "#!/usr/bin/env python3", Some(Language::Python)),
+		(
+			"script",
+			"#!/usr/bin/env -S python3.12 -u",
+			Some(Language::Python),
+		),
+		("script", "#!/bin/bash", Some(Language::Shell)),
+		// Unknown languages aren't highlighted
+		("notes.txt", "", None),
+		("script", "#!/usr/bin/perl", None),
+	];
+	for (filename, first_line, language) in detected {
+		assert_eq!(Language::detect(filename, first_line), language);
+	}
+}
+
+// Test highlighting the tokens of a line of Rust
+#[test]
+fn highlight_rust() {
+	let line = "#[test] pub fn main() -> Vec<u8> { let c = '\\n'; println!(\"{}\", 0x1F); } // end";
+	assert_eq!(
+		tokens_of(Language::Rust, line, SyntaxState::Normal),
+		vec![
+			("#[test]", TokenKind::Attribute),
+			("pub", TokenKind::Keyword),
+			("fn", TokenKind::Keyword),
+			("main", TokenKind::Function),
+			("Vec", TokenKind::Type),
+			("u8", TokenKind::Type),
+			("let", TokenKind::Keyword),
+			("'\\n'", TokenKind::String),
+			("println!", TokenKind::Attribute),
+			("\"{}\"", TokenKind::String),
+			("0x1F", TokenKind::Number),
+			("// end", TokenKind::Comment),
+		]
+	);
+
+	// Lifetimes aren't char literals and ranges aren't decimals
+	assert_eq!(
+		tokens_of(Language::Rust, "&'a str; 0..10", SyntaxState::Normal),
+		vec![
+			("'a", TokenKind::Attribute),
+			("str", TokenKind::Type),
+			("0", TokenKind::Number),
+			("10", TokenKind::Number),
+		]
+	);
+}
+
+// Test highlighting keys, tables, and values in TOML and JSON
+#[test]
+fn highlight_keys_and_values() {
+	assert_eq!(
+		tokens_of(Language::Toml, "[workspace.package]", SyntaxState::Normal),
+		vec![("[workspace.package]", TokenKind::Type)]
+	);
+	assert_eq!(
+		tokens_of(
+			Language::Toml,
+			"ratatui.version = \"0.26\" # comment",
+			SyntaxState::Normal
+		),
+		vec![
+			("ratatui", TokenKind::Key),
+			("version", TokenKind::Key),
+			("\"0.26\"", TokenKind::String),
+			("# comment", TokenKind::Comment),
+		]
+	);
+	assert_eq!(
+		tokens_of(
+			Language::Json,
+			"{\"soft_wrap\": true, \"width\": 4}",
+			SyntaxState::Normal
+		),
+		vec![
+			("\"soft_wrap\"", TokenKind::Key),
+			("true", TokenKind::Constant),
+			("\"width\"", TokenKind::Key),
+			("4", TokenKind::Number),
+		]
+	);
+}
+
+// Test highlighting SQL (whose keywords can be in any case)
+#[test]
+fn highlight_sql() {
+	assert_eq!(
+		tokens_of(
+			Language::Sql,
+			"SELECT name FROM users where id = 'it''s' AND x IS NULL; -- done",
+			SyntaxState::Normal
+		),
+		vec![
+			("SELECT", TokenKind::Keyword),
+			("FROM", TokenKind::Keyword),
+			("where", TokenKind::Keyword),
+			("'it'", TokenKind::String),
+			("'s'", TokenKind::String),
+			("AND", TokenKind::Keyword),
+			("IS", TokenKind::Keyword),
+			("NULL", TokenKind::Constant),
+			("-- done", TokenKind::Comment),
+		]
+	);
+}
+
+// Test highlighting shell variables and comments
+#[test]
+fn highlight_shell() {
+	assert_eq!(
+		tokens_of(
+			Language::Shell,
+			"if [ $# -gt 0 ]; then echo \"${1}\"#no; fi # done",
+			SyntaxState::Normal
+		),
+		vec![
+			("if", TokenKind::Keyword),
+			("$#", TokenKind::Attribute),
+			("0", TokenKind::Number),
+			("then", TokenKind::Keyword),
+			("\"${1}\"", TokenKind::String),
+			("fi", TokenKind::Keyword),
+			("# done", TokenKind::Comment),
+		]
+	);
+}
+
+// Test that multi-line constructs carry their state onto the next lines
+#[test]
+fn highlight_multiline_state() {
+	// Nested block comments in Rust
+	let first = syntax::highlight_line(Language::Rust, "/* outer /* inner */", SyntaxState::Normal);
+	assert_eq!(first.end_state, SyntaxState::BlockComment(1));
+	assert_eq!(
+		tokens_of(Language::Rust, "end */ fn", first.end_state),
+		vec![("end */", TokenKind::Comment), ("fn", TokenKind::Keyword)]
+	);
+
+	// Triple quoted strings in Python
+	let first = syntax::highlight_line(Language::Python, "doc = \"\"\"start", SyntaxState::Normal);
+	assert_eq!(
+		tokens_of(Language::Python, "end\"\"\" if x", first.end_state),
+		vec![("end\"\"\"", TokenKind::String), ("if", TokenKind::Keyword)]
+	);
+
+	// Fenced code blocks in Markdown
+	let fence = syntax::highlight_line(Language::Markdown, "```rust", SyntaxState::Normal);
+	assert_eq!(fence.end_state, SyntaxState::CodeFence);
+	assert_eq!(
+		tokens_of(Language::Markdown, "# not a heading", fence.end_state),
+		vec![("# not a heading", TokenKind::String)]
+	);
+	let end = syntax::highlight_line(Language::Markdown, "```", fence.end_state);
+	assert_eq!(end.end_state, SyntaxState::Normal);
+}
+
+// Test highlighting the inline elements of Markdown
+#[test]
+fn highlight_markdown() {
+	assert_eq!(
+		tokens_of(Language::Markdown, "## Usage", SyntaxState::Normal),
+		vec![("## Usage", TokenKind::Heading)]
+	);
+	assert_eq!(
+		tokens_of(
+			Language::Markdown,
+			"- Run `cargo build` for **release** builds of snake_case_names [docs](https://docs.rs)",
+			SyntaxState::Normal
+		),
+		vec![
+			("-", TokenKind::Keyword),
+			("`cargo build`", TokenKind::String),
+			("**release**", TokenKind::Emphasis),
+			("[docs](https://docs.rs)", TokenKind::Link),
+		]
+	);
+}
+
+// Test that only edited lines (and lines whose starting state changes) are highlighted again
+#[test]
+fn incremental_highlighting() {
+	// Make an editor for the SMALL_FILE (highlighted as Rust)
+	let mut editor = construct_editor(SMALL_FILE);
+	editor.language = Some(Language::Rust);
+	highlight_blocks(editor.blocks.as_mut().unwrap());
+	let original = editor.blocks.as_ref().unwrap().blocks_list[0]
+		.highlights
+		.clone();
+	assert!(original.iter().all(Option::is_some));
+
+	// Edit the third line
+	down_arrow(&mut editor);
+	down_arrow(&mut editor);
+	char_key(&mut editor, '/');
+	char_key(&mut editor, '*');
+
+	// Only the edited line's highlighting is cleared
+	let highlights = &editor.blocks.as_ref().unwrap().blocks_list[0].highlights;
+	for (idx, highlight) in highlights.iter().enumerate() {
+		assert_eq!(highlight.is_none(), idx == 2);
+	}
+
+	// The unclosed comment changes the highlighting of the lines after it
+	let blocks = editor.blocks.as_mut().unwrap();
+	highlight_blocks(blocks);
+	let highlights = &blocks.blocks_list[0].highlights;
+	assert_eq!(highlights[..2], original[..2]);
+	let line = blocks.get_some_line(3).unwrap();
+	assert_eq!(
+		tokens_of(
+			Language::Rust,
+			&line,
+			highlights[3].as_ref().unwrap().start_state
+		),
+		vec![(line.as_str(), TokenKind::Comment)]
+	);
+	assert_eq!(
+		highlights[3].as_ref().unwrap().start_state,
+		SyntaxState::BlockComment(1)
+	);
+}
+
+// Test highlighting blocks that start inside a block comment opened in an unloaded block
+#[test]
+fn highlight_unloaded_comment() {
+	// Write a block comment that spans several blocks to a test file
+	let filename = "../editor/test_files/highlight_unloaded-debug-test.txt";
+	let body = vec!["let x = (1, \"two\");"; 2000].join("\n");
+	std::fs::write(filename, format!("/*\n{}\n*/\nfn main() {{}}\n", body)).unwrap();
+	// Make an editor for the test file (highlighted as Rust)
+	let mut editor = construct_editor(filename);
+	std::fs::remove_file(filename).unwrap();
+	editor.language = Some(Language::Rust);
+
+	// Jump to the end of the comment (unloading the blocks before it without highlighting them)
+	jump_to(&mut editor, 2001, 0);
+	let mut blocks = editor.blocks.clone().unwrap();
+	assert!(blocks.head_block > 0);
+	editor.update_highlights(&mut blocks);
+
+	// The first loaded line is still in the comment
+	let highlight = blocks.blocks_list[0].highlights[0].as_ref().unwrap();
+	assert_eq!(highlight.start_state, SyntaxState::BlockComment(1));
+	let line = blocks.get_some_line(blocks.starting_line_num).unwrap();
+	assert_eq!(
+		tokens_of(Language::Rust, &line, highlight.start_state),
+		vec![(line.as_str(), TokenKind::Comment)]
+	);
+	// The code after the comment is highlighted as code
+	let last_block = blocks.blocks_list.last().unwrap();
+	let highlight = last_block.highlights[last_block.len - 2].as_ref().unwrap();
+	assert_eq!(highlight.start_state, SyntaxState::Normal);
+	assert_eq!(highlight.tokens[0].kind, TokenKind::Keyword);
+}
+
+/*
+==========================================
+			TREE-SITTER TESTS
+==========================================
+*/
+
+// Lines of Rust used to test the syntax tree
+#[cfg(feature = "tree-sitter")]
+const RUST_LINES: [&str; 7] = [
+	"impl Square {",
+	"\tfn area(&self) -> u32 {",
+	"\t\tlet text = \"multi",
+	"line\";",
+	"\t\tself.side * self.side",
+	"\t}",
+	"}",
+];
+
+// Get the text and kind of the tokens of a line highlighted with a syntax tree
+#[cfg(feature = "tree-sitter")]
+fn tree_tokens<'a>(
+	tree: &syntax::SyntaxTree,
+	line: &'a str,
+	line_num: usize,
+) -> Vec<(&'a str, TokenKind)> {
+	tree.highlight_line(line_num)
+		.iter()
+		.map(|token| (&line[token.start..token.end], token.kind))
+		.collect()
+}
+
+// Test highlighting lines with the tree-sitter grammar
+#[cfg(feature = "tree-sitter")]
+#[test]
+fn tree_sitter_highlighting() {
+	let mut tree = syntax::SyntaxTree::new(Language::Rust).unwrap();
+	// All of the lines are highlighted when first parsed (starting at line 10)
+	assert_eq!(tree.update(&RUST_LINES, 10), vec![10..17]);
+
+	let tokens = tree_tokens(&tree, RUST_LINES[1], 11);
+	assert!(tokens.contains(&("fn", TokenKind::Keyword)));
+	assert!(tokens.contains(&("area", TokenKind::Function)));
+	assert!(tokens.contains(&("u32", TokenKind::Type)));
+	// Strings that span multiple lines are highlighted on each line
+	assert_eq!(
+		tree_tokens(&tree, RUST_LINES[3], 13)[0],
+		("line\"", TokenKind::String)
+	);
+
+	// Nothing changes if the text doesn't change
+	assert!(tree.update(&RUST_LINES, 10).is_empty());
+	// Edited lines are reported as changed
+	let mut lines = RUST_LINES;
+	lines[4] = "\t\tself.side * 2";
+	let changed = tree.update(&lines, 10);
+	assert!(changed.iter().any(|range| range.contains(&14)));
+	assert!(!changed.iter().any(|range| range.contains(&10)));
+}
+
+// Test the structural queries of the syntax tree
+#[cfg(feature = "tree-sitter")]
+#[test]
+fn tree_sitter_structure() {
+	let mut tree = syntax::SyntaxTree::new(Language::Rust).unwrap();
+	tree.update(&RUST_LINES, 0);
+
+	// Inside of the method
+	let function = tree.current_function(4, 3).unwrap();
+	assert_eq!(function.kind, "function_item");
+	assert_eq!(function.name.as_deref(), Some("area"));
+	assert_eq!((function.start, function.end), ([1, 1], [2, 5]));
+	let block = tree.enclosing_block(4, 3).unwrap();
+	assert_eq!((block.kind, block.start), ("block", [23, 1]));
+
+	// Outside of the method (but in the impl block)
+	assert_eq!(tree.current_function(0, 0), None);
+	assert_eq!(tree.enclosing_block(6, 0).unwrap().kind, "declaration_list");
+}
+
+// Test that the editor keeps its syntax tree in sync with edits
+#[cfg(feature = "tree-sitter")]
+#[test]
+fn tree_sitter_editor() {
+	// Write the Rust lines to a test file
+	let filename = "../editor/test_files/tree_sitter-debug-test.rs";
+	std::fs::write(filename, RUST_LINES.join("\n")).unwrap();
+	// Make an editor for the test file (which is parsed when it is rendered)
+	let mut editor = construct_editor(filename);
+	std::fs::remove_file(filename).unwrap();
+	editor.get_paragraph();
+
+	// Move into the method
+	for _i in 0..4 {
+		down_arrow(&mut editor);
+	}
+	editor.get_paragraph();
+	assert_eq!(
+		editor.current_function().unwrap().name.as_deref(),
+		Some("area")
+	);
+
+	// Rename the method
+	up_arrow(&mut editor);
+	up_arrow(&mut editor);
+	up_arrow(&mut editor);
+	home_key(&mut editor, true);
+	for _i in 0..8 {
+		right_arrow(&mut editor, true);
+	}
+	char_key(&mut editor, 's');
+	editor.get_paragraph();
+	assert_eq!(
+		editor.current_function().unwrap().name.as_deref(),
+		Some("areas")
+	);
+}
@@ -7,8 +7,8 @@ use stack_choice::StackChoice;
 const UNDO_PERIOD: usize = 20;
 
 /* Undo or Redo state. Formatted as (stored position, text position,
-cursor position, scroll offset, Blocks, Selection) */
-pub type UnRedoState = (usize, usize, [usize; 2], usize, Blocks, Selection);
+cursor position, scroll offset, Blocks, Selection, file length) */
+pub type UnRedoState = (usize, usize, [usize; 2], usize, Blocks, Selection, usize);
 
 // Controls both the undo and redo stack simultaneously
 pub struct UnRedoStack {
@@ -18,6 +18,8 @@ pub struct UnRedoStack {
 	redo_stack: Stack<UnRedoState>,
 	// The counter towards progress of updating the stack
 	counter: usize,
+	// Flag for not adding any states (e.g. while editing at each of multiple cursors)
+	is_paused: bool,
 }
 
 impl UnRedoStack {
@@ -27,23 +29,36 @@ impl UnRedoStack {
 			undo_stack: Stack::new(),
 			redo_stack: Stack::new(),
 			counter: 0,
+			is_paused: false,
 		}
 	}
 
-	// Automatically update the undo and redo stacks, and the cached state
-	pub fn auto_update(&mut self, state: UnRedoState, force: bool) {
+	// Create an undo/redo stack that doesn't add any states
+	pub fn paused() -> Self {
+		Self {
+			is_paused: true,
+			..Self::new()
+		}
+	}
+
+	/* Automatically update the undo and redo stacks, and the cached state.
+	The state is only taken (and copied) when it's pushed to the undo stack. */
+	pub fn auto_update(&mut self, get_state: impl FnOnce() -> UnRedoState, force: bool) {
+		if self.is_paused {
+			return;
+		}
 		self.counter += 1;
 		// If the stack is empty, push to it
 		if self.undo_stack.is_empty() {
 			self.counter = 0;
-			self.undo_stack.push(state);
+			self.undo_stack.push(get_state());
 		/* If the counter has reached the required value and the top of the stack is different from
 		the current cached Blocks, update both stacks */
 		} else if self.counter >= UNDO_PERIOD || force {
 			// Reset the counter
 			self.counter = 0;
 			// Push the cached state to the undo stack
-			self.undo_stack.push(state);
+			self.undo_stack.push(get_state());
 			// Clear the redo stack
 			self.redo_stack.clear();
 		}
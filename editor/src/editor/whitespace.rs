@@ -0,0 +1,164 @@
+use config::config::{Config, Whitespace};
+use ratatui::{
+	style::{Modifier, Style},
+	text::Span,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+// Shown in place of spaces
+const SPACE_MARKER: &str = "\u{00b7}";
+// Shown at the start of tabs
+const TAB_MARKER: &str = "\u{2192}";
+// Shown in place of non-breaking spaces
+const NBSP_MARKER: &str = "\u{237d}";
+// Shown at the start of each level of indentation
+const INDENT_GUIDE: &str = "\u{2502}";
+
+// Determines how the whitespace of a single line is displayed
+pub struct WhitespaceRenderer<'a> {
+	// The user's config (whitespace mode, indent guides, tab width, and theme)
+	config: &'a Config,
+	// Byte index of the end of the line's indentation
+	indent_end: usize,
+	// Byte index of the start of the line's trailing whitespace
+	trailing_start: usize,
+	// Byte indices of the indentation that start an indent guide
+	guides: Vec<usize>,
+	// Byte indices of graphemes that follow zero-width characters
+	zero_width: Vec<usize>,
+}
+
+impl<'a> WhitespaceRenderer<'a> {
+	// Create a renderer for the given line of text
+	pub fn new(line: &str, config: &'a Config) -> Self {
+		let indent_end = line.len() - line.trim_start().len();
+		let trailing_start = line.trim_end().len();
+
+		// Each level of indentation starts at a multiple of the tab width
+		let mut guides = Vec::new();
+		let mut column = 0;
+		for (loc, grapheme) in line[..indent_end].grapheme_indices(true) {
+			if grapheme == "\t" || column % config.tab_width == 0 {
+				guides.push(loc);
+			}
+			column += match grapheme {
+				"\t" => config.tab_width,
+				_ => UnicodeWidthStr::width(grapheme),
+			};
+		}
+
+		// Zero-width characters can't be shown, so the grapheme after them is marked instead
+		let mut zero_width = Vec::new();
+		let mut graphemes = line.grapheme_indices(true).peekable();
+		while let Some((loc, grapheme)) = graphemes.next() {
+			if is_zero_width(grapheme) {
+				// The last grapheme of the line marks itself
+				zero_width.push(graphemes.peek().map_or(loc, |(next, _)| *next));
+			}
+		}
+
+		WhitespaceRenderer {
+			config,
+			indent_end,
+			trailing_start,
+			guides,
+			zero_width,
+		}
+	}
+
+	// Get the text displayed for the grapheme at the given byte index (and the style of any marker)
+	pub fn display(&self, loc: usize, grapheme: &str) -> (String, Style) {
+		let theme = &self.config.theme;
+		// Style of visible whitespace
+		let marker = Style::default().fg(theme.whitespace_fg);
+		// Trailing whitespace is highlighted when any whitespace is visible
+		let trailing = self.config.whitespace != Whitespace::Hidden && loc >= self.trailing_start;
+		let marker = match trailing {
+			true => marker.bg(theme.trailing_whitespace_bg),
+			false => marker,
+		};
+		// Flag for whether spaces and tabs at this position are shown
+		let visible = match self.config.whitespace {
+			Whitespace::Hidden => false,
+			Whitespace::Trailing => trailing,
+			Whitespace::All => true,
+		};
+		// Flag for whether an indent guide starts at this position
+		let guide = self.config.indent_guides && self.guides.contains(&loc);
+		let guide_style = Style::default().fg(theme.indent_guide_fg);
+		// The spaces that fill the rest of a tab
+		let tab_fill = " ".repeat(self.config.tab_width.saturating_sub(1));
+
+		let (text, style) = match grapheme {
+			"\t" if visible => (format!("{}{}", TAB_MARKER, tab_fill), marker),
+			"\t" if guide => (format!("{}{}", INDENT_GUIDE, tab_fill), guide_style),
+			"\t" => (" ".repeat(self.config.tab_width), Style::default()),
+			" " if visible => (String::from(SPACE_MARKER), marker),
+			" " if guide => (String::from(INDENT_GUIDE), guide_style),
+			"\u{a0}" | "\u{202f}" if self.config.whitespace != Whitespace::Hidden => {
+				(String::from(NBSP_MARKER), marker)
+			}
+			_ if trailing => (String::from(grapheme), marker),
+			_ => (String::from(grapheme), Style::default()),
+		};
+
+		// Underline graphemes that follow zero-width characters
+		match self.config.whitespace != Whitespace::Hidden && self.zero_width.contains(&loc) {
+			true => (
+				text,
+				style
+					.fg(theme.whitespace_fg)
+					.add_modifier(Modifier::UNDERLINED),
+			),
+			false => (text, style),
+		}
+	}
+
+	// Split a segment of the line (starting at the given byte index) into styled spans
+	pub fn spans(&self, start: usize, text: &str, style: Style) -> Vec<Span<'static>> {
+		// Text without any markers can be a single span
+		if !self.needs_markers(start, text) {
+			return vec![Span::styled(String::from(text), style)];
+		}
+
+		let mut spans = Vec::new();
+		// Consecutive graphemes that are displayed as they are
+		let mut plain = String::new();
+		for (loc, grapheme) in text.grapheme_indices(true) {
+			let (display, marker) = self.display(start + loc, grapheme);
+			if marker == Style::default() && display == grapheme {
+				plain.push_str(grapheme);
+			} else {
+				if !plain.is_empty() {
+					spans.push(Span::styled(std::mem::take(&mut plain), style));
+				}
+				spans.push(Span::styled(display, style.patch(marker)));
+			}
+		}
+		if !plain.is_empty() {
+			spans.push(Span::styled(plain, style));
+		}
+
+		spans
+	}
+
+	// Check if a segment of the line has any whitespace that's displayed differently
+	fn needs_markers(&self, start: usize, text: &str) -> bool {
+		text.contains(['\t', '\u{a0}', '\u{202f}'])
+			|| start < self.indent_end
+			|| start + text.len() > self.trailing_start
+			|| self
+				.zero_width
+				.iter()
+				.any(|loc| (start..start + text.len()).contains(loc))
+			|| (self.config.whitespace == Whitespace::All && text.contains(' '))
+	}
+}
+
+// Check if a grapheme is made of only zero-width characters (e.g. zero-width spaces and joiners)
+fn is_zero_width(grapheme: &str) -> bool {
+	grapheme
+		.chars()
+		.all(|c| matches!(c, '\u{200b}'..='\u{200d}' | '\u{2060}' | '\u{feff}'))
+}
@@ -3,17 +3,19 @@
 pub mod editor {
 
 	use std::{
-		fs::{read_to_string, File, OpenOptions},
-		io::Error,
+		fs::{File, OpenOptions},
+		io::{Error, Write},
 		path::Path,
 		rc::Rc,
 		time::Duration,
 	};
 
-	use cli_clipboard::{ClipboardContext, ClipboardProvider};
-	use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+	use crossterm::event::{
+		self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+		MouseEventKind,
+	};
 	use ratatui::{
-		layout::Rect,
+		layout::{Margin, Rect},
 		style::Style,
 		text::{Line, Span, Text},
 		widgets::{Block, BorderType, Borders, Paragraph},
@@ -26,45 +28,93 @@ pub mod editor {
 	use unicode_segmentation::UnicodeSegmentation;
 
 	use blocks::Blocks;
-	use config::config::Config;
+	use clipboard::{Clipboard, RegisterAction};
+	use config::config::{AutoIndent, Config, LineNumbers};
+	use file_backing::{Compression, FileBacking};
 	use key_functions::{
-		copy_paste, editing_keys,
+		block_selection::{self, BlockSelection},
+		bracket_match, comment_keys, copy_paste, editing_keys,
 		highlight_keys::{self, selection::Selection},
+		indent::{self, Indentation},
+		line_keys,
+		mouse::{self, Click},
+		multi_cursor::{self, Cursor},
 		navigation_keys, save_key,
+		select_keys::{self, Expansion},
 	};
+	use status_bar::StatusMessage;
+	#[cfg(feature = "tree-sitter")]
+	pub use syntax::SyntaxNode;
+	#[cfg(feature = "tree-sitter")]
+	use syntax::SyntaxTree;
+	use syntax::{Language, Token, TokenKind};
 	use unredo_stack::{stack_choice::StackChoice, UnRedoStack, UnRedoState};
+	use whitespace::WhitespaceRenderer;
 
 	/// Module containing the `Blocks` structure.
 	/// This `Blocks` structure loads in multiple text blocks at once.
 	mod blocks;
+	/// Module containing the `Clipboard` structure.
+	/// Text is copied to the system clipboard, the terminal (OSC 52), or an internal clipboard.
+	mod clipboard;
+	/// Module containing the `FileBacking` enum.
+	/// The `FileBacking` is either a file on disk or an in-memory buffer.
+	mod file_backing;
+	/// Module for horizontally scrolling (clipping) lines that are wider than the widget.
+	mod horizontal_scroll;
 	/// Subroutines for the `handle_input` function.
 	/// The `handle_input` function takes keyboard input and performs an action.
 	mod input_handlers;
 	/// Module containing all the logic of each key and key combination.
 	mod key_functions;
+	/// Module for drawing column rulers and finding text past the line length limit.
+	mod rulers;
+	/// Module for soft wrapping long lines across multiple rows of the widget.
+	mod soft_wrap;
+	/// Module for building the status bar (file, cursor position, and transient messages).
+	mod status_bar;
+	/// Module for syntax highlighting the text of supported languages.
+	/// The language of a file is detected by its extension (or shebang).
+	mod syntax;
 	/// Module containing the `UnRedoStack` structure which handles
 	/// both undo and redo states for the editor.
 	mod unredo_stack;
+	/// Module for displaying visible whitespace and indentation guides.
+	mod whitespace;
 	// Testing module found at crate/src/editor/tests.rs
 	#[cfg(test)]
 	mod tests;
 
 	// 300 millisecond pollrate for reading terminal events
 	const POLLRATE: u64 = 300;
+	/// The filename that tells the editor to read its text from stdin
+	pub const STDIN_FILENAME: &str = "-";
 
 	/// The struct for the editing space of the app.
 	/// Each `EditorSpace` opens its own file and handles the IO for editing.
 	pub struct EditorSpace {
+		// The rectangular block of text that is selected (if there is one)
+		block_selection: Option<BlockSelection>,
 		// Object containing multiple text blocks
 		blocks: Option<Blocks>,
+		// The most recent click of the mouse (used for double and triple clicks)
+		click: Option<Click>,
 		// The clipboard to copy from and paste to
-		clipboard: Option<ClipboardContext>,
+		clipboard: Clipboard,
 		/// The config of the editor. Currently, it only sets the tab width.
 		pub config: Config,
+		// The compression format of the open file (if it is compressed)
+		compression: Option<Compression>,
 		// Position of cursor on the screen
 		cursor_position: [usize; 2],
-		// The file that is open
-		file: File,
+		// The extra cursors in the text (for editing in multiple places at once)
+		cursors: Vec<Cursor>,
+		// The name of the encoding of the file
+		encoding: &'static str,
+		// The selections that were expanded (to shrink back to)
+		expansions: Vec<Expansion>,
+		// The file that is open (or the in-memory buffer read from stdin)
+		file: FileBacking,
 		// Name of file opened in current editor space
 		filename: String,
 		// The number of lines in the entire file
@@ -73,10 +123,25 @@ pub mod editor {
 		height: usize,
 		// Position used to access indices within graphemes vectors
 		is_initialized: bool,
+		// The whitespace that the file is indented with
+		indentation: Indentation,
+		// Used to scroll the text horizontally on screen (in display columns)
+		horizontal_offset: usize,
+		// The language of the file (used for syntax highlighting)
+		language: Option<Language>,
+		// Flag for the terminal sending some control keys as others (e.g. Ctrl+/ as Ctrl+7)
+		legacy_keys: bool,
+		// The name of the line ending used by the file
+		line_ending: &'static str,
+		// The syntax tree of the loaded text (if there's a tree-sitter grammar for the language)
+		#[cfg(feature = "tree-sitter")]
+		syntax_tree: Option<SyntaxTree>,
 		// Used to scroll the text on screen (and calculate line number)
 		scroll_offset: usize,
 		// Structure keeping track of the highlighted selection of text
 		selection: Selection,
+		// The transient message shown in the status bar
+		status_message: Option<StatusMessage>,
 		// Used to store the horizontal position in the text
 		stored_position: usize,
 		// Actual position on the current line of text
@@ -94,27 +159,49 @@ pub mod editor {
 	impl EditorSpace {
 		/// Create a new EditorSpace
 		pub fn new(filename: String, config: Config) -> Self {
-			// Open (and create if necessary) the given file
-			let file = Self::open_file(&filename);
-			// Create a clipboard
-			let clipboard = match ClipboardContext::new() {
-				Ok(clip) => Some(clip),
-				Err(_) => None,
+			// Read the text from stdin or open (and create if necessary) the given file
+			let (file, compression) = match filename.as_str() {
+				STDIN_FILENAME => (
+					FileBacking::from_stdin()
+						.unwrap_or_else(|err| panic!("Couldn't read from stdin | {}", err)),
+					None,
+				),
+				// Compressed files are decompressed into an in-memory buffer
+				_ => FileBacking::from_file(Self::open_file(&filename))
+					.unwrap_or_else(|err| panic!("Couldn't read {} | {}", filename, err)),
 			};
+			// Create the preferred clipboard
+			let clipboard = Clipboard::new(config.clipboard);
+			// Indent as set by the config (until the file's indentation is detected)
+			let indentation = Indentation::from_config(&config);
 			// Construct an EditorSpace
 			EditorSpace {
+				block_selection: None,
 				blocks: None,
+				click: None,
 				clipboard,
 				config,
+				compression,
 				cursor_position: [0, 0],
+				cursors: Vec::new(),
+				encoding: "UTF-8",
+				expansions: Vec::new(),
 				file,
 				filename,
 				file_length: 0,
 				height: 0,
+				horizontal_offset: 0,
 				is_initialized: false,
+				indentation,
+				language: None,
+				legacy_keys: false,
+				line_ending: "LF",
 				scroll_offset: 0,
 				selection: Selection::new(),
+				status_message: None,
 				stored_position: 0,
+				#[cfg(feature = "tree-sitter")]
+				syntax_tree: None,
 				text_position: 0,
 				unredo_stack: UnRedoStack::new(),
 				widget_horz_bounds: (0, 0),
@@ -128,26 +215,55 @@ pub mod editor {
 			// Non-blocking read
 			if event::poll(Duration::from_millis(POLLRATE)).unwrap() {
 				// Read input
-				if let Event::Key(KeyEvent {
-					code,
-					modifiers,
-					kind: KeyEventKind::Press,
-					..
-				}) = event::read().unwrap()
-				{
-					// If no modifier key is pressed
-					if modifiers.is_empty() {
-						input_handlers::no_modifiers(self, code);
-					// If the Shift modifier is pressed
-					} else if modifiers == KeyModifiers::SHIFT {
-						input_handlers::shift_modifier(self, code);
-					// If the Control modifier is pressed
-					} else if modifiers == KeyModifiers::CONTROL {
-						input_handlers::control_modifier(self, code, break_loop);
-					// If Control and Shift modifiers are both pressed
-					} else if modifiers == (KeyModifiers::CONTROL | KeyModifiers::SHIFT) {
-						input_handlers::control_and_shift_modifiers(self, code);
+				match event::read().unwrap() {
+					// Key presses
+					Event::Key(KeyEvent {
+						code,
+						modifiers,
+						kind: KeyEventKind::Press,
+						..
+					}) => {
+						// Cycling through the kill ring only continues from a paste
+						self.clipboard.start_input();
+						// If waiting for the name of a register, the key names it
+						if let Some(action) = self.clipboard.pending_register.take() {
+							input_handlers::register_key(self, action, code);
+						// If no modifier key is pressed
+						} else if modifiers.is_empty() {
+							input_handlers::no_modifiers(self, code);
+						// If the Shift modifier is pressed
+						} else if modifiers == KeyModifiers::SHIFT {
+							input_handlers::shift_modifier(self, code);
+						// If the Control modifier is pressed
+						} else if modifiers == KeyModifiers::CONTROL {
+							input_handlers::control_modifier(self, code, break_loop);
+						// If Control and Shift modifiers are both pressed
+						} else if modifiers == (KeyModifiers::CONTROL | KeyModifiers::SHIFT) {
+							input_handlers::control_and_shift_modifiers(self, code);
+						// If the Alt modifier is pressed
+						} else if modifiers == KeyModifiers::ALT {
+							input_handlers::alt_modifier(self, code);
+						// If Control and Alt modifiers are both pressed
+						} else if modifiers == (KeyModifiers::CONTROL | KeyModifiers::ALT) {
+							input_handlers::control_and_alt_modifiers(self, code);
+						// If Alt and Shift modifiers are both pressed
+						} else if modifiers == (KeyModifiers::ALT | KeyModifiers::SHIFT) {
+							input_handlers::alt_and_shift_modifiers(self, code);
+						}
 					}
+					// Clicking, dragging, and scrolling with the mouse
+					Event::Mouse(event) => input_handlers::mouse_input(self, event),
+					// Text pasted into the terminal (with bracketed paste)
+					Event::Paste(text) => {
+						self.clipboard.start_input();
+						multi_cursor::edit(self, true, |editor| {
+							copy_paste::paste_text(editor, &text)
+						});
+					}
+					/* The layout is recomputed when the next frame is drawn
+					(which updates the size of the widget in render_ui) */
+					Event::Resize(_, _) => (),
+					_ => (),
 				}
 			}
 		}
@@ -162,12 +278,29 @@ pub mod editor {
 					layout[1].width as usize,
 					layout[1].height as usize,
 				);
+			/* Otherwise, keep up with the layout (e.g. when the terminal is resized
+			or the width of the line numbers changes) */
+			} else {
+				self.update_bounds(layout[1]);
 			}
 
+			// Keep the cursor's visual row within the widget when soft wrapping
+			if self.config.soft_wrap && !self.is_empty() {
+				self.scroll_to_wrapped_cursor();
+			// Otherwise, scroll horizontally to keep the cursor within the widget
+			} else {
+				self.horizontal_offset = horizontal_scroll::follow_cursor(
+					self.horizontal_offset,
+					self.cursor_position[0],
+					self.width,
+				);
+			}
+			// Get the position of the cursor on screen
+			let (cursor_x, cursor_y) = self.get_screen_cursor();
 			// Set the cursor position on screen
 			frame.set_cursor(
-				(self.cursor_position[0] + self.widget_horz_bounds.0 + 1) as u16,
-				(self.cursor_position[1] + self.widget_vert_bounds.0 + 1) as u16,
+				(cursor_x + self.widget_horz_bounds.0 + 1) as u16,
+				(cursor_y + self.widget_vert_bounds.0 + 1) as u16,
 			);
 
 			// If the editor is empty, render an empty widget
@@ -181,6 +314,129 @@ pub mod editor {
 			}
 		}
 
+		/// Check if the text of the editor was piped in through stdin
+		pub fn is_piped(&self) -> bool {
+			self.filename == STDIN_FILENAME && self.file.is_in_memory()
+		}
+
+		/// Get the name of the language being syntax highlighted (if the language was detected)
+		pub fn language_name(&self) -> Option<&'static str> {
+			self.language.map(Language::name)
+		}
+
+		/// Get the function that the cursor is in (as of the last time the editor was rendered).
+		/// This requires the `tree-sitter` feature and a tree-sitter grammar for the file's language.
+		#[cfg(feature = "tree-sitter")]
+		pub fn current_function(&self) -> Option<SyntaxNode> {
+			let line_num = self.get_line_num(self.cursor_position[1]);
+			self.syntax_tree
+				.as_ref()?
+				.current_function(line_num, self.text_position)
+		}
+
+		/// Get the innermost block that the cursor is in (as of the last time the editor was rendered).
+		/// This requires the `tree-sitter` feature and a tree-sitter grammar for the file's language.
+		#[cfg(feature = "tree-sitter")]
+		pub fn enclosing_block(&self) -> Option<SyntaxNode> {
+			let line_num = self.get_line_num(self.cursor_position[1]);
+			self.syntax_tree
+				.as_ref()?
+				.enclosing_block(line_num, self.text_position)
+		}
+
+		/// Get the width of the widget that displays the line numbers.
+		/// This is 2 greater than the number of digits of the last line number (0 if hidden).
+		pub fn gutter_width(&self) -> u16 {
+			match self.config.line_numbers {
+				LineNumbers::Hidden => 0,
+				_ => self.file_length.max(1).to_string().len() as u16 + 2,
+			}
+		}
+
+		/// Take the escape sequence that copies text to the terminal's clipboard (OSC 52).
+		/// This needs to be written to the terminal when the system clipboard isn't used.
+		pub fn take_clipboard_sequence(&mut self) -> Option<String> {
+			self.clipboard.take_osc52()
+		}
+
+		/// Set whether the terminal sends some control keys as others (e.g. Ctrl+/ as Ctrl+7).
+		/// Terminals do this when they don't support keyboard enhancements.
+		pub fn set_legacy_keys(&mut self, legacy_keys: bool) {
+			self.legacy_keys = legacy_keys;
+		}
+
+		/// Render the status bar (the file, cursor position, and transient message) in the given area
+		pub fn render_status_bar(&self, frame: &mut Frame, area: Rect) {
+			let theme = &self.config.theme;
+			// The name of the file (and whether it has been modified)
+			let modified = match self.is_modified() {
+				true => " [+]",
+				false => "",
+			};
+			let mut left = vec![Span::from(format!(" {}{}", self.display_name(), modified))];
+			// The transient message (if it hasn't expired)
+			if let Some(message) = self.status_message.as_ref().filter(|msg| msg.is_visible()) {
+				left.push(Span::styled(
+					format!("  {}", message.text),
+					Style::default().fg(theme.status_message_fg),
+				));
+			}
+			// The cursor position and information about the file
+			let right = format!(
+				"Ln {}, Col {} | {} lines | {} | {} | {} | {} ",
+				self.get_line_num(self.cursor_position[1]) + 1,
+				self.cursor_position[0] + 1,
+				self.file_length,
+				self.encoding,
+				self.line_ending,
+				self.indent_mode(),
+				self.language_name().unwrap_or("Plain Text"),
+			);
+
+			// The status bar has borders on the sides (to line up with the editor)
+			let width = area.width.saturating_sub(2) as usize;
+			frame.render_widget(
+				Paragraph::new(status_bar::build_line(left, right, width))
+					.style(
+						Style::default()
+							.fg(theme.status_bar_fg)
+							.bg(theme.status_bar_bg),
+					)
+					.block(
+						Block::new()
+							.borders(Borders::LEFT | Borders::RIGHT)
+							.border_type(BorderType::Thick),
+					),
+				area,
+			);
+		}
+
+		/// Show a transient message in the status bar
+		pub fn set_status_message(&mut self, message: impl Into<String>) {
+			self.status_message = Some(StatusMessage::new(message.into()));
+		}
+
+		/// Check if any of the text has been modified since it was opened (or saved)
+		pub fn is_modified(&self) -> bool {
+			self.blocks
+				.as_ref()
+				.is_some_and(|blocks| blocks.blocks_list.iter().any(|block| block.is_modified))
+		}
+
+		/// Write the final text of the in-memory buffer (including any unsaved edits) to the given output.
+		/// This is used to write the final buffer to stdout when used as a filter.
+		pub fn write_buffer(&mut self, output: &mut impl Write) -> Result<(), Error> {
+			// Save any unsaved edits to the buffer first
+			if self.blocks.is_some() {
+				save_key::save_key_combo(self, false, "");
+			}
+			// Get the bytes of the buffer
+			let contents = self.file.contents()?;
+			// Write the buffer to the output
+			output.write_all(&contents)?;
+			output.flush()
+		}
+
 		// Initialize the editor
 		fn init_editor(
 			&mut self,
@@ -194,6 +450,12 @@ pub mod editor {
 			self.init_file_length();
 			// Create the first block of text in Blocks
 			self.init_first_block()?;
+			// Detect the language of the file for syntax highlighting
+			self.init_language();
+			// Detect whether the file is indented with tabs or spaces
+			if self.config.detect_indent {
+				self.indentation = indent::detect_indentation(self);
+			}
 			// Return the string "Success" (arbitrary)
 			Ok("Success")
 		}
@@ -218,17 +480,21 @@ pub mod editor {
 		}
 
 		// Return the vector as a paragraph
-		fn get_paragraph(&mut self) -> Paragraph {
+		fn get_paragraph(&mut self) -> Paragraph<'_> {
 			// Clone the blocks
 			let mut blocks = self.blocks.as_ref().unwrap().clone();
 			// Check the blocks are valid
 			blocks.check_blocks(self);
+			// Highlight the syntax of any lines that have changed
+			self.update_highlights(&mut blocks);
 			// Set the editor blocks to this new blocks
 			self.blocks = Some(blocks.clone());
 			// The current line number in the blocks
 			let line_num = self.get_line_num(self.cursor_position[1]) - blocks.starting_line_num;
+			// The bracket at the cursor and its match (which are highlighted)
+			let brackets = bracket_match::matching_brackets(self, &blocks);
 			// Get the lines of the currently loaded blocks as a vector
-			let mut lines = self.get_lines_from_blocks(blocks, line_num);
+			let mut lines = self.get_lines_from_blocks(blocks, &brackets);
 
 			// Highlight the line that the cursor is on
 			if let Some(line) = lines.get(line_num) {
@@ -239,22 +505,171 @@ pub mod editor {
 				);
 			}
 
+			// If soft wrapping, split the visible lines into rows (no need to scroll)
+			if self.config.soft_wrap {
+				return Paragraph::new(Text::from(self.wrap_lines(lines, line_num)));
+			}
+
+			// Clip the visible lines to the columns that are shown in the widget
+			let lines: Vec<Line> = lines
+				.into_iter()
+				.enumerate()
+				.skip(self.scroll_offset)
+				.take(self.height + 1)
+				.map(|(idx, line)| {
+					let line =
+						horizontal_scroll::clip_line(line, self.horizontal_offset, self.width);
+					// Pad the cursor's line so the entire line is highlighted
+					match idx == line_num {
+						true => self.pad_line(line),
+						false => line,
+					}
+				})
+				.collect();
+
 			// Return a paragraph from the lines
-			Paragraph::new(Text::from(lines)).scroll((self.scroll_offset as u16, 0))
+			Paragraph::new(Text::from(lines))
+		}
+
+		// Pad a line with blank space to the width of the widget (for line highlighting)
+		fn pad_line<'a>(&self, mut line: Line<'a>) -> Line<'a> {
+			let blank_space = " ".repeat(self.width.saturating_sub(line.width()));
+			line.spans.push(Span::from(blank_space));
+			line
 		}
 
 		// Return a Paragraph of the line numbers that are displayed
-		fn get_line_numbers_paragraph(&self) -> Paragraph {
+		fn get_line_numbers_paragraph(&self) -> Paragraph<'_> {
+			// If soft wrapping, only show the line number on the first row of each line
+			if self.config.soft_wrap {
+				return Paragraph::new(Text::from(self.get_wrapped_line_numbers()));
+			}
+
 			// Construct a vector of line numbers
 			let line_nums: Vec<Line> = self
 				.get_line_numbers()
 				.into_par_iter()
-				.map(|num| Line::from(format!("{}", num)))
+				.map(|num| Line::from(self.get_line_number_label(num)))
 				.collect();
 
 			Paragraph::new(Text::from(line_nums))
 		}
 
+		// Get the text displayed for the given line number (starting at 1) in the line numbers widget
+		fn get_line_number_label(&self, num: usize) -> String {
+			// The line number of the cursor's line (starting at 1)
+			let cursor_num = self.get_line_num(self.cursor_position[1]) + 1;
+			match self.config.line_numbers {
+				LineNumbers::Absolute => num.to_string(),
+				LineNumbers::Relative => num.abs_diff(cursor_num).to_string(),
+				// Only the cursor's line shows its absolute line number
+				LineNumbers::Hybrid if num == cursor_num => num.to_string(),
+				LineNumbers::Hybrid => num.abs_diff(cursor_num).to_string(),
+				LineNumbers::Hidden => String::new(),
+			}
+		}
+
+		// Get the wrap points of a line of text (where each of its visual rows start)
+		fn get_wrap_points(&self, line: &str) -> Vec<(usize, usize)> {
+			soft_wrap::wrap_points(line, self.width, self.config.tab_width)
+		}
+
+		// Get the wrap points of each line that is (at least partially) visible in the widget
+		fn get_visible_wrap_points(&self) -> Vec<Vec<(usize, usize)>> {
+			let blocks = self.blocks.as_ref().unwrap();
+			// Wrap points of the visible lines
+			let mut visible = Vec::new();
+			// The number of visual rows of the visible lines
+			let mut num_rows = 0;
+			// The line number of the top line of the widget
+			let mut line_num = self.get_line_num(0);
+
+			// Loop until the widget is full (or there are no more loaded lines)
+			while num_rows <= self.height {
+				let line = match blocks.get_some_line(line_num) {
+					Ok(line) => line,
+					Err(_) => break,
+				};
+				let points = self.get_wrap_points(&line);
+				num_rows += points.len();
+				visible.push(points);
+				line_num += 1;
+			}
+
+			visible
+		}
+
+		// Split the visible lines into visual rows to be displayed in the widget
+		fn wrap_lines<'a>(&self, lines: Vec<Line<'a>>, line_num: usize) -> Vec<Line<'a>> {
+			// The visual rows of the widget
+			let mut rows = Vec::new();
+			// Split each visible line about its wrap points
+			for ((idx, line), points) in lines
+				.into_iter()
+				.enumerate()
+				.skip(self.scroll_offset)
+				.zip(self.get_visible_wrap_points())
+			{
+				let line_rows = soft_wrap::split_line(line, &points);
+				// Pad the rows of the cursor's line so the entire line is highlighted
+				match idx == line_num {
+					true => rows.extend(line_rows.into_iter().map(|row| self.pad_line(row))),
+					false => rows.extend(line_rows),
+				}
+			}
+
+			rows
+		}
+
+		// Get the line numbers for soft wrapped lines (blank on each row after the first)
+		fn get_wrapped_line_numbers(&self) -> Vec<Line<'_>> {
+			// The line number of the top line of the widget
+			let top_line = self.get_line_num(0);
+			// The line number followed by blank lines for each extra row
+			let mut line_nums = Vec::new();
+			for (idx, points) in self.get_visible_wrap_points().iter().enumerate() {
+				line_nums.push(Line::from(self.get_line_number_label(top_line + idx + 1)));
+				line_nums.extend((1..points.len()).map(|_| Line::from("")));
+			}
+
+			line_nums
+		}
+
+		// Get the position of the cursor on the screen (accounting for soft wrapping)
+		fn get_screen_cursor(&self) -> (usize, usize) {
+			// Without soft wrapping, each line is one row (that may be scrolled horizontally)
+			if !self.config.soft_wrap || self.is_empty() {
+				return (
+					self.cursor_position[0].saturating_sub(self.horizontal_offset),
+					self.cursor_position[1],
+				);
+			}
+
+			// The number of visual rows of the lines above the cursor
+			let rows_above: usize = self
+				.get_visible_wrap_points()
+				.iter()
+				.take(self.cursor_position[1])
+				.map(Vec::len)
+				.sum();
+			// The visual row of the cursor on its line
+			let points = self.get_wrap_points(&self.blocks.as_ref().unwrap().get_current_line());
+			let row = soft_wrap::row_of(&points, self.text_position);
+
+			(
+				self.cursor_position[0].saturating_sub(points[row].1),
+				rows_above + row,
+			)
+		}
+
+		// Scroll down until the cursor's visual row is within the widget (for soft wrapping)
+		fn scroll_to_wrapped_cursor(&mut self) {
+			while self.cursor_position[1] > 0 && self.get_screen_cursor().1 > self.height {
+				self.scroll_offset += 1;
+				self.cursor_position[1] -= 1;
+			}
+		}
+
 		// Delete a highlighted selection of text
 		fn delete_selection(&mut self) {
 			// Start point of the selection (as an immutable tuple)
@@ -262,8 +677,8 @@ pub mod editor {
 			// End point of the selection (as an immutable tuple)
 			let end = (self.selection.end[0], self.selection.end[1]);
 
-			// Clone the blocks
-			let mut blocks = self.blocks.as_ref().unwrap().clone();
+			// Take the blocks (they're set back after the selection is deleted)
+			let mut blocks = self.blocks.take().unwrap();
 			// Create the remaining line after deleting the selection
 			let remaining_line = Self::construct_remaining_line(&mut blocks, start, end);
 			// Update the first line of the selection
@@ -294,10 +709,10 @@ pub mod editor {
 			self.widget_vert_bounds = (start.1, start.1 + height);
 			/* Track the height of the widget (subtract three because there is a top and
 			bottom boundary plus an extra line that isn't included in the height) */
-			self.height = self.widget_vert_bounds.1 - self.widget_vert_bounds.0 - 3;
+			self.height = height.saturating_sub(3);
 			/* Track the width of the widget (subtract 2 for the side borders of the
 			widget) */
-			self.width = self.widget_horz_bounds.1 - self.widget_horz_bounds.0 - 2;
+			self.width = width.saturating_sub(2);
 
 			// Set the cursor to the beginning of the block
 			self.cursor_position = [0, 0];
@@ -306,14 +721,51 @@ pub mod editor {
 			self.is_initialized = true;
 		}
 
+		// Update the bounds (and size) of the widget to the given area
+		fn update_bounds(&mut self, area: Rect) {
+			self.widget_horz_bounds = (area.x as usize, (area.x + area.width) as usize);
+			self.widget_vert_bounds = (area.y as usize, (area.y + area.height) as usize);
+			// Subtract 2 for the side borders of the widget
+			self.width = (area.width as usize).saturating_sub(2);
+
+			// The height of the widget (same as in init_starting_position)
+			let height = (area.height as usize).saturating_sub(3);
+			// If the height changed (e.g. the terminal was resized), keep the cursor in the widget
+			if height != self.height {
+				self.height = height;
+				self.clamp_cursor();
+			}
+		}
+
+		// Keep the cursor's line within the widget (and fill the widget with text) after it's resized
+		fn clamp_cursor(&mut self) {
+			// Scroll down until the cursor is within the widget
+			while self.cursor_position[1] > self.height {
+				self.scroll_offset += 1;
+				self.cursor_position[1] -= 1;
+			}
+			// Scroll up to show lines above instead of empty rows after the end of the file
+			while self.scroll_offset > 0
+				&& self.cursor_position[1] < self.height
+				&& self.get_line_num(self.height) >= self.file_length
+			{
+				self.scroll_offset -= 1;
+				self.cursor_position[1] += 1;
+			}
+		}
+
 		// Initialize the file length variable
 		fn init_file_length(&mut self) {
+			// Get the bytes of the file (or in-memory buffer)
+			let contents = self.file.contents().unwrap();
+			// Detect the encoding of the file
+			self.encoding = status_bar::detect_encoding(&contents);
+			// The text of the file
+			let text = String::from_utf8_lossy(&contents);
+			// Detect the line ending used by the file
+			self.line_ending = status_bar::detect_line_ending(&text);
 			// Get the lines of the file (with their newline chars)
-			let lines: Vec<String> = read_to_string(&self.filename)
-				.unwrap()
-				.split_inclusive('\n')
-				.map(String::from)
-				.collect();
+			let lines: Vec<String> = text.split_inclusive('\n').map(String::from).collect();
 			// Count the number of lines in the file
 			self.file_length = lines.par_iter().count();
 
@@ -334,6 +786,74 @@ pub mod editor {
 			Ok(0)
 		}
 
+		// The name of the open file shown to the user
+		fn display_name(&self) -> &str {
+			match self.is_piped() {
+				true => "[stdin]",
+				false => &self.filename,
+			}
+		}
+
+		// The way lines are indented (shown in the status bar)
+		fn indent_mode(&self) -> String {
+			match self.indentation {
+				Indentation::Tabs => format!("Tabs: {}", self.config.tab_width),
+				Indentation::Spaces(width) => format!("Spaces: {}", width),
+			}
+		}
+
+		// Detect the language of the file from its name or its first line (shebang)
+		fn init_language(&mut self) {
+			// The first line of the file
+			let first_line = self
+				.blocks
+				.as_ref()
+				.unwrap()
+				.get_some_line(0)
+				.unwrap_or_default();
+			self.language = Language::detect(&self.filename, &first_line);
+			// Parse the file with its tree-sitter grammar
+			#[cfg(feature = "tree-sitter")]
+			{
+				self.syntax_tree = self.language.and_then(SyntaxTree::new);
+			}
+		}
+
+		// Highlight the syntax of any lines in the Blocks that have changed
+		fn update_highlights(&mut self, blocks: &mut Blocks) {
+			let Some(language) = self.language else {
+				return;
+			};
+
+			// Highlight the lines using the syntax tree (if there's a tree-sitter grammar)
+			#[cfg(feature = "tree-sitter")]
+			if let Some(tree) = self.syntax_tree.as_mut() {
+				// The loaded lines of text
+				let lines: Vec<&str> = blocks
+					.blocks_list
+					.iter()
+					.flat_map(|block| block.content.iter().map(String::as_str))
+					.collect();
+				// Update the tree and highlight any lines it changed
+				for lines in tree.update(&lines, blocks.starting_line_num) {
+					blocks.clear_highlights(lines);
+				}
+				blocks.update_highlights(|line_num, _, state| syntax::LineHighlight {
+					start_state: state,
+					tokens: tree.highlight_line(line_num),
+					end_state: state,
+				});
+				return;
+			}
+
+			let highlighter = |_, line: &str, state| syntax::highlight_line(language, line, state);
+			// A block comment or string may already be open at the start of the loaded blocks
+			blocks
+				.update_head_state(self, highlighter)
+				.unwrap_or_else(|err| panic!("Couldn't highlight the previous blocks | {}", err));
+			blocks.update_highlights(highlighter);
+		}
+
 		// Render a blank ui if there are no TextBlocks in the editor Blocks
 		fn render_empty_ui(&self, layout: Rc<[Rect]>, frame: &mut Frame) {
 			// If the file is empty, render an empty line numbers widget
@@ -363,79 +883,167 @@ pub mod editor {
 				),
 				layout[1],
 			);
+
+			// Draw the rulers over the text (inside of the borders)
+			rulers::draw_rulers(
+				frame.buffer_mut(),
+				layout[1].inner(&Margin::new(1, 1)),
+				&self.config.rulers,
+				self.horizontal_offset,
+				self.config.theme.ruler_bg,
+				self.config.theme.selection_highlight,
+			);
+			// Draw the extra cursors over the text
+			multi_cursor::draw_cursors(
+				self,
+				frame.buffer_mut(),
+				layout[1].inner(&Margin::new(1, 1)),
+			);
 		}
 
 		// Get the lines of text from the Blocks content
-		fn get_lines_from_blocks(&self, blocks: Blocks, line_num: usize) -> Vec<Line> {
-			// Convert the blocks into one text vector
-			let mut text: Vec<String> = Vec::new();
+		fn get_lines_from_blocks(&self, blocks: Blocks, brackets: &[[usize; 2]]) -> Vec<Line<'_>> {
+			// Convert the blocks into one vector of lines (with their syntax highlighting)
+			let mut text: Vec<(String, Vec<Token>)> = Vec::new();
 			// Iterate through the blocks that are currently loaded in
 			for block in blocks.blocks_list {
+				// The highlighted tokens of each line (if there are any)
+				let tokens = block.highlights.into_iter().map(|highlight| {
+					highlight
+						.map(|highlight| highlight.tokens)
+						.unwrap_or_default()
+				});
 				// Add all of the lines in these blocks into the `text` vector
-				text.par_extend(block.content);
+				text.extend(block.content.into_iter().zip(tokens));
 			}
 
+			// The byte index of each matching bracket on the lines
+			let brackets: Vec<[usize; 2]> = brackets
+				.iter()
+				.map(|[position, line_num]| [*position, line_num - blocks.starting_line_num])
+				.collect();
+
 			// Create a vector of Lines from the text
 			text.into_par_iter()
 				.enumerate()
-				.map(|(idx, line)| {
-					// If the line is empty, return a blank line
-					if line.is_empty() {
-						// Blank space to add to put on the line (for line highlighting)
-						let blank_space = String::from(&" ".repeat(self.width));
-						// Return the blank line
-						return Line::from(blank_space);
-					// The line the cursor is on
-					} else if idx == line_num {
-						// Length of the text on the line
-						let len = line.graphemes(true).count();
-						// Only create blank space if there is room
-						let blank_space = match len < self.width {
-							true => &" ".repeat(self.width - len),
-							false => &String::new(),
-						};
-						// Return the line
-						return self.parse_line(idx, &(line + blank_space));
-					}
-					self.parse_line(idx, &line)
+				.map(|(idx, (line, tokens))| {
+					// The matching brackets on this line
+					let mut brackets: Vec<usize> = brackets
+						.iter()
+						.filter(|[_, line_num]| *line_num == idx)
+						.map(|[position, _]| *position)
+						.collect();
+					brackets.sort();
+					self.parse_line(idx, &line, &tokens, &brackets)
 				})
 				.collect()
 		}
 
 		// Create a Line struct from the given String line
-		fn parse_line(&self, idx: usize, line: &str) -> Line {
+		fn parse_line(
+			&self,
+			idx: usize,
+			line: &str,
+			tokens: &[Token],
+			brackets: &[usize],
+		) -> Line<'_> {
 			// Top line of the widget
 			let top_line = self.scroll_offset;
 			// The bottom line of the widget
 			let bottom_line = self.height + self.scroll_offset;
-			// Start tab with a vertical line
-			let mut tab_char = String::from("\u{2502}");
-			// Iterator to create a string of tab_width - 1 number of spaces
-			tab_char.push_str(&" ".repeat(self.config.tab_width - 1));
+			// Displays the whitespace of the line (tabs, indent guides, and visible whitespace)
+			let whitespace = WhitespaceRenderer::new(line, &self.config);
+			// Byte index of the text past the line length limit (if there is any)
+			let limit = self
+				.config
+				.line_limit
+				.and_then(|limit| rulers::limit_index(line, limit, self.config.tab_width));
+			// Style of the text past the line length limit
+			let long_line = Style::default().fg(self.config.theme.long_line_fg);
 
 			// Only highlight if selection isn't empty (and its within the widget's bounds)
 			if !self.selection.is_empty && idx >= top_line && idx <= bottom_line {
 				// Highlight characters
-				return self.highlight_line(idx, line);
+				return self.highlight_line(idx, line, tokens, &whitespace, limit);
 			}
 
-			Line::from(String::from(line).replace('\t', &tab_char))
+			// Byte index of the start of each segment
+			let mut position = 0;
+			// Color each segment of the line by its syntax
+			let spans: Vec<Span> =
+				bracket_match::split_brackets(syntax::segments(line, tokens), brackets)
+					.into_iter()
+					.flat_map(|(text, kind)| {
+						let style = match kind {
+							Some(kind) => Style::default().fg(kind.color(&self.config.theme)),
+							None => Style::default(),
+						};
+						// Highlight the matching brackets
+						let style = match brackets.contains(&position) {
+							true => style.bg(self.config.theme.bracket_match_bg),
+							false => style,
+						};
+						let start = position;
+						position += text.len();
+						match limit {
+							// The segment is split by the line length limit
+							Some(limit) if start < limit && limit < position => {
+								let (before, after) = text.split_at(limit - start);
+								let mut spans = whitespace.spans(start, before, style);
+								spans.extend(whitespace.spans(
+									limit,
+									after,
+									style.patch(long_line),
+								));
+								spans
+							}
+							// The entire segment is past the limit
+							Some(limit) if limit <= start => {
+								whitespace.spans(start, text, style.patch(long_line))
+							}
+							_ => whitespace.spans(start, text, style),
+						}
+					})
+					.collect();
+
+			Line::from(spans)
 		}
 
 		// Highlight a line of text
-		fn highlight_line(&self, idx: usize, line: &str) -> Line {
+		fn highlight_line(
+			&self,
+			idx: usize,
+			line: &str,
+			tokens: &[Token],
+			whitespace: &WhitespaceRenderer,
+			limit: Option<usize>,
+		) -> Line<'_> {
 			// Indices for highlighting within the paragraph
 			let (start_line, end_line) = self.calc_highlight_indices();
-			// Start tab with a vertical line
-			let mut tab_char = String::from("\u{2502}");
-			// Iterator to create a string of tab_width - 1 number of spaces
-			tab_char.push_str(&" ".repeat(self.config.tab_width - 1));
 			// A vector of the graphemes as stylized spans
 			let graphemes: Vec<Span> = line
 				.grapheme_indices(true)
 				.map(|(loc, character)| {
+					// The displayed text of the grapheme (and the style of any whitespace marker)
+					let (character, marker) = whitespace.display(loc, character);
 					// Highlight the grapheme
-					self.highlight_grapheme(idx, loc, character, &tab_char, start_line, end_line)
+					let span = self.highlight_grapheme(idx, loc, character, start_line, end_line);
+					// The selection's highlight takes priority over the trailing whitespace's
+					let marker = match span.style.bg {
+						Some(_) => Style { bg: None, ..marker },
+						None => marker,
+					};
+					// Color the grapheme by its syntax (and mark whitespace)
+					let span = span
+						.patch_style(syntax::style_at(tokens, loc, &self.config.theme))
+						.patch_style(marker);
+					// Color the grapheme if it's past the line length limit
+					match limit {
+						Some(limit) if loc >= limit => {
+							span.patch_style(Style::default().fg(self.config.theme.long_line_fg))
+						}
+						_ => span,
+					}
 				})
 				.collect();
 
@@ -447,33 +1055,32 @@ pub mod editor {
 			&self,
 			idx: usize,
 			loc: usize,
-			character: &str,
-			tab_char: &str,
+			character: String,
 			start_line: usize,
 			end_line: usize,
-		) -> Span {
+		) -> Span<'_> {
 			if idx == start_line && start_line == end_line {
-				self.highlight_one_line(loc, String::from(character).replace('\t', tab_char))
+				self.highlight_one_line(loc, character)
 			// If on first line (and there are multiple lines in selection)
 			} else if idx == start_line {
 				// Highlight character
-				self.highlight_first_line(loc, String::from(character).replace('\t', tab_char))
+				self.highlight_first_line(loc, character)
 			// If on last line (and there are multiple lines in selection)
 			} else if idx == end_line {
 				// Highlight character
-				self.highlight_last_line(loc, String::from(character).replace('\t', tab_char))
+				self.highlight_last_line(loc, character)
 			// If between first and last line in multine selection
 			} else if idx > start_line && idx < end_line {
-				Span::from(String::from(character).replace('\t', tab_char))
+				Span::from(character)
 					.style(Style::default().bg(self.config.theme.selection_highlight))
 			// If not in selection
 			} else {
-				Span::from(String::from(character).replace('\t', tab_char))
+				Span::from(character)
 			}
 		}
 
 		// Highlight character on one line and return them as a Span
-		fn highlight_one_line(&self, loc: usize, character: String) -> Span {
+		fn highlight_one_line(&self, loc: usize, character: String) -> Span<'_> {
 			// If within selection, highlight character
 			if loc >= self.selection.start[0] && loc < self.selection.end[0] {
 				Span::from(character)
@@ -484,7 +1091,7 @@ pub mod editor {
 		}
 
 		// Highlight character if on the first line of a multiline selection
-		fn highlight_first_line(&self, loc: usize, character: String) -> Span {
+		fn highlight_first_line(&self, loc: usize, character: String) -> Span<'_> {
 			// Highlight all characters on the line after the cursor
 			if loc >= self.selection.start[0] {
 				Span::from(character)
@@ -495,7 +1102,7 @@ pub mod editor {
 		}
 
 		// Highlight character if on the last line of a multiline selection
-		fn highlight_last_line(&self, loc: usize, character: String) -> Span {
+		fn highlight_last_line(&self, loc: usize, character: String) -> Span<'_> {
 			// Highlight all characters on the line before the cursor
 			if loc < self.selection.end[0] {
 				Span::from(character)
@@ -590,6 +1197,14 @@ pub mod editor {
 			}
 		}
 
+		// Add the current state of the editor to the undo stack (if it's time for a new undo state)
+		fn update_unredo_stack(&mut self, force: bool) {
+			// Take the stack so that the editor's state can be read while it's updated
+			let mut unredo_stack = std::mem::replace(&mut self.unredo_stack, UnRedoStack::paused());
+			unredo_stack.auto_update(|| self.get_unredo_state(), force);
+			self.unredo_stack = unredo_stack;
+		}
+
 		// Get the current state of the editor (to be added to the unredo stack)
 		fn get_unredo_state(&self) -> UnRedoState {
 			(
@@ -599,6 +1214,7 @@ pub mod editor {
 				self.scroll_offset,
 				self.blocks.as_ref().unwrap().clone(),
 				self.selection.clone(),
+				self.file_length,
 			)
 		}
 	}
@@ -0,0 +1,4 @@
+def main():
+  if True:
+    print("Indented with two spaces")
+  return 0